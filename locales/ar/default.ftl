@@ -0,0 +1,93 @@
+accept = قبول
+cancel = إلغاء
+days-ago =
+    { $count ->
+        [one] منذ يوم واحد
+        [two] منذ يومين
+        [few] منذ { $count } أيام
+       *[other] منذ { $count } يوم
+    }
+hours-ago =
+    { $count ->
+        [one] منذ ساعة واحدة
+        [two] منذ ساعتين
+        [few] منذ { $count } ساعات
+       *[other] منذ { $count } ساعة
+    }
+in-days =
+    { $count ->
+        [one] خلال يوم واحد
+        [two] خلال يومين
+        [few] خلال { $count } أيام
+       *[other] خلال { $count } يوم
+    }
+in-hours =
+    { $count ->
+        [one] خلال ساعة واحدة
+        [two] خلال ساعتين
+        [few] خلال { $count } ساعات
+       *[other] خلال { $count } ساعة
+    }
+in-minutes =
+    { $count ->
+        [one] خلال دقيقة واحدة
+        [two] خلال دقيقتين
+        [few] خلال { $count } دقائق
+       *[other] خلال { $count } دقيقة
+    }
+in-months =
+    { $count ->
+        [one] خلال شهر واحد
+        [two] خلال شهرين
+        [few] خلال { $count } أشهر
+       *[other] خلال { $count } شهر
+    }
+in-seconds =
+    { $count ->
+        [one] خلال ثانية واحدة
+        [two] خلال ثانيتين
+        [few] خلال { $count } ثوانٍ
+       *[other] خلال { $count } ثانية
+    }
+in-years =
+    { $count ->
+        [one] خلال سنة واحدة
+        [two] خلال سنتين
+        [few] خلال { $count } سنوات
+       *[other] خلال { $count } سنة
+    }
+just-now = الآن
+loading = جارٍ التحميل…
+loading-failed = تعذّر تحميل العناصر
+minutes-ago =
+    { $count ->
+        [one] منذ دقيقة واحدة
+        [two] منذ دقيقتين
+        [few] منذ { $count } دقائق
+       *[other] منذ { $count } دقيقة
+    }
+months-ago =
+    { $count ->
+        [one] منذ شهر واحد
+        [two] منذ شهرين
+        [few] منذ { $count } أشهر
+       *[other] منذ { $count } شهر
+    }
+no-items = لا يوجد شيء هنا بعد
+ok = حسنًا
+retry = إعادة المحاولة
+seconds-ago =
+    { $count ->
+        [one] منذ ثانية واحدة
+        [two] منذ ثانيتين
+        [few] منذ { $count } ثوانٍ
+       *[other] منذ { $count } ثانية
+    }
+submit = إرسال
+years-ago =
+    { $count ->
+        [one] منذ سنة واحدة
+        [two] منذ سنتين
+        [few] منذ { $count } سنوات
+       *[other] منذ { $count } سنة
+    }
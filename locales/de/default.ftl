@@ -0,0 +1,69 @@
+accept = Akzeptieren
+cancel = Abbrechen
+days-ago =
+    { $count ->
+        [one] vor { $count } Tag
+       *[other] vor { $count } Tagen
+    }
+hours-ago =
+    { $count ->
+        [one] vor { $count } Stunde
+       *[other] vor { $count } Stunden
+    }
+in-days =
+    { $count ->
+        [one] in { $count } Tag
+       *[other] in { $count } Tagen
+    }
+in-hours =
+    { $count ->
+        [one] in { $count } Stunde
+       *[other] in { $count } Stunden
+    }
+in-minutes =
+    { $count ->
+        [one] in { $count } Minute
+       *[other] in { $count } Minuten
+    }
+in-months =
+    { $count ->
+        [one] in { $count } Monat
+       *[other] in { $count } Monaten
+    }
+in-seconds =
+    { $count ->
+        [one] in { $count } Sekunde
+       *[other] in { $count } Sekunden
+    }
+in-years =
+    { $count ->
+        [one] in { $count } Jahr
+       *[other] in { $count } Jahren
+    }
+just-now = gerade eben
+loading = Wird geladen…
+loading-failed = Die Einträge konnten nicht geladen werden
+minutes-ago =
+    { $count ->
+        [one] vor { $count } Minute
+       *[other] vor { $count } Minuten
+    }
+months-ago =
+    { $count ->
+        [one] vor { $count } Monat
+       *[other] vor { $count } Monaten
+    }
+no-items = Hier gibt es noch nichts
+ok = OK
+retry = Erneut versuchen
+seconds-ago =
+    { $count ->
+        [one] vor { $count } Sekunde
+       *[other] vor { $count } Sekunden
+    }
+submit = Absenden
+years-ago =
+    { $count ->
+        [one] vor { $count } Jahr
+       *[other] vor { $count } Jahren
+    }
@@ -1,3 +1,69 @@
 accept = Accept
 cancel = Cancel
+days-ago =
+    { $count ->
+        [one] { $count } day ago
+       *[other] { $count } days ago
+    }
+hours-ago =
+    { $count ->
+        [one] { $count } hour ago
+       *[other] { $count } hours ago
+    }
+in-days =
+    { $count ->
+        [one] in { $count } day
+       *[other] in { $count } days
+    }
+in-hours =
+    { $count ->
+        [one] in { $count } hour
+       *[other] in { $count } hours
+    }
+in-minutes =
+    { $count ->
+        [one] in { $count } minute
+       *[other] in { $count } minutes
+    }
+in-months =
+    { $count ->
+        [one] in { $count } month
+       *[other] in { $count } months
+    }
+in-seconds =
+    { $count ->
+        [one] in { $count } second
+       *[other] in { $count } seconds
+    }
+in-years =
+    { $count ->
+        [one] in { $count } year
+       *[other] in { $count } years
+    }
+just-now = just now
+loading = Loading…
+loading-failed = Could not load the items
+minutes-ago =
+    { $count ->
+        [one] { $count } minute ago
+       *[other] { $count } minutes ago
+    }
+months-ago =
+    { $count ->
+        [one] { $count } month ago
+       *[other] { $count } months ago
+    }
+no-items = There is nothing here yet
+ok = Ok
+retry = Retry
+seconds-ago =
+    { $count ->
+        [one] { $count } second ago
+       *[other] { $count } seconds ago
+    }
 submit = Submit
+years-ago =
+    { $count ->
+        [one] { $count } year ago
+       *[other] { $count } years ago
+    }
@@ -0,0 +1,69 @@
+accept = Aceptar
+cancel = Cancelar
+days-ago =
+    { $count ->
+        [one] hace { $count } día
+       *[other] hace { $count } días
+    }
+hours-ago =
+    { $count ->
+        [one] hace { $count } hora
+       *[other] hace { $count } horas
+    }
+in-days =
+    { $count ->
+        [one] dentro de { $count } día
+       *[other] dentro de { $count } días
+    }
+in-hours =
+    { $count ->
+        [one] dentro de { $count } hora
+       *[other] dentro de { $count } horas
+    }
+in-minutes =
+    { $count ->
+        [one] dentro de { $count } minuto
+       *[other] dentro de { $count } minutos
+    }
+in-months =
+    { $count ->
+        [one] dentro de { $count } mes
+       *[other] dentro de { $count } meses
+    }
+in-seconds =
+    { $count ->
+        [one] dentro de { $count } segundo
+       *[other] dentro de { $count } segundos
+    }
+in-years =
+    { $count ->
+        [one] dentro de { $count } año
+       *[other] dentro de { $count } años
+    }
+just-now = ahora mismo
+loading = Cargando…
+loading-failed = No se pudieron cargar los elementos
+minutes-ago =
+    { $count ->
+        [one] hace { $count } minuto
+       *[other] hace { $count } minutos
+    }
+months-ago =
+    { $count ->
+        [one] hace { $count } mes
+       *[other] hace { $count } meses
+    }
+no-items = Aún no hay nada aquí
+ok = Ok
+retry = Reintentar
+seconds-ago =
+    { $count ->
+        [one] hace { $count } segundo
+       *[other] hace { $count } segundos
+    }
+submit = Enviar
+years-ago =
+    { $count ->
+        [one] hace { $count } año
+       *[other] hace { $count } años
+    }
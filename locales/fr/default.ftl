@@ -0,0 +1,69 @@
+accept = Accepter
+cancel = Annuler
+days-ago =
+    { $count ->
+        [one] il y a { $count } jour
+       *[other] il y a { $count } jours
+    }
+hours-ago =
+    { $count ->
+        [one] il y a { $count } heure
+       *[other] il y a { $count } heures
+    }
+in-days =
+    { $count ->
+        [one] dans { $count } jour
+       *[other] dans { $count } jours
+    }
+in-hours =
+    { $count ->
+        [one] dans { $count } heure
+       *[other] dans { $count } heures
+    }
+in-minutes =
+    { $count ->
+        [one] dans { $count } minute
+       *[other] dans { $count } minutes
+    }
+in-months =
+    { $count ->
+        [one] dans { $count } mois
+       *[other] dans { $count } mois
+    }
+in-seconds =
+    { $count ->
+        [one] dans { $count } seconde
+       *[other] dans { $count } secondes
+    }
+in-years =
+    { $count ->
+        [one] dans { $count } an
+       *[other] dans { $count } ans
+    }
+just-now = à l’instant
+loading = Chargement…
+loading-failed = Impossible de charger les éléments
+minutes-ago =
+    { $count ->
+        [one] il y a { $count } minute
+       *[other] il y a { $count } minutes
+    }
+months-ago =
+    { $count ->
+        [one] il y a { $count } mois
+       *[other] il y a { $count } mois
+    }
+no-items = Il n’y a encore rien ici
+ok = OK
+retry = Réessayer
+seconds-ago =
+    { $count ->
+        [one] il y a { $count } seconde
+       *[other] il y a { $count } secondes
+    }
+submit = Envoyer
+years-ago =
+    { $count ->
+        [one] il y a { $count } an
+       *[other] il y a { $count } ans
+    }
@@ -0,0 +1,69 @@
+accept = Aceitar
+cancel = Cancelar
+days-ago =
+    { $count ->
+        [one] há { $count } dia
+       *[other] há { $count } dias
+    }
+hours-ago =
+    { $count ->
+        [one] há { $count } hora
+       *[other] há { $count } horas
+    }
+in-days =
+    { $count ->
+        [one] em { $count } dia
+       *[other] em { $count } dias
+    }
+in-hours =
+    { $count ->
+        [one] em { $count } hora
+       *[other] em { $count } horas
+    }
+in-minutes =
+    { $count ->
+        [one] em { $count } minuto
+       *[other] em { $count } minutos
+    }
+in-months =
+    { $count ->
+        [one] em { $count } mês
+       *[other] em { $count } meses
+    }
+in-seconds =
+    { $count ->
+        [one] em { $count } segundo
+       *[other] em { $count } segundos
+    }
+in-years =
+    { $count ->
+        [one] em { $count } ano
+       *[other] em { $count } anos
+    }
+just-now = agora mesmo
+loading = Carregando…
+loading-failed = Não foi possível carregar os itens
+minutes-ago =
+    { $count ->
+        [one] há { $count } minuto
+       *[other] há { $count } minutos
+    }
+months-ago =
+    { $count ->
+        [one] há { $count } mês
+       *[other] há { $count } meses
+    }
+no-items = Ainda não há nada aqui
+ok = Ok
+retry = Tentar novamente
+seconds-ago =
+    { $count ->
+        [one] há { $count } segundo
+       *[other] há { $count } segundos
+    }
+submit = Enviar
+years-ago =
+    { $count ->
+        [one] há { $count } ano
+       *[other] há { $count } anos
+    }
@@ -1,8 +1,11 @@
+use std::fmt::{self, Display};
 use std::sync::LazyLock;
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-pub(crate) static SESSION_CONFIG: LazyLock<SessionConfig> = LazyLock::new(|| extract_from_env("SESSION_"));
+pub(crate) static PAGINATION_CONFIG: LazyLock<PaginationConfig> = LazyLock::new(|| extract_config("PAGINATION_"));
+pub(crate) static SESSION_CONFIG: LazyLock<SessionConfig> = LazyLock::new(|| extract_config("SESSION_"));
 
 pub fn load_config() {
     use env_logger::{Env, init_from_env};
@@ -12,36 +15,469 @@ pub fn load_config() {
     init_from_env(Env::new().default_filter_or("info"));
 }
 
-pub fn extract_from_env<'a, T>(prefix: &str) -> T
+pub fn extract_config<T>(prefix: &str) -> T
 where
-    T: Deserialize<'a> + Serialize + Default,
+    T: DeserializeOwned + Serialize + Default,
+{
+    try_extract_config(prefix).unwrap_or_else(|error| panic!("{error}"))
+}
+
+pub fn try_extract_config<T>(prefix: &str) -> Result<T, ConfigError>
+where
+    T: DeserializeOwned + Serialize + Default,
+{
+    try_extract(prefix, config_figment(prefix))
+}
+
+fn config_figment(prefix: &str) -> figment::Figment {
+    use figment::providers::{Env, Format, Toml};
+    use figment::{Figment, Profile};
+
+    let section = prefix.trim_end_matches('_').to_lowercase();
+    let file = Figment::from(Toml::file(std::env::var("DOT_CONFIG").unwrap_or("dot.toml".to_owned())).nested())
+        .focus(&section);
+
+    Figment::new()
+        .merge(file)
+        .merge(Env::prefixed(prefix).global())
+        .select(Profile::from_env_or("DOT_PROFILE", Profile::Default))
+}
+
+pub fn extract_from_env<T>(prefix: &str) -> T
+where
+    T: DeserializeOwned + Serialize + Default,
+{
+    try_extract_from_env(prefix).unwrap_or_else(|error| panic!("{error}"))
+}
+
+pub fn try_extract_from_env<T>(prefix: &str) -> Result<T, ConfigError>
+where
+    T: DeserializeOwned + Serialize + Default,
 {
     use figment::Figment;
-    use figment::providers::{Env, Serialized};
+    use figment::providers::Env;
+
+    try_extract(prefix, Figment::from(Env::prefixed(prefix)))
+}
+
+fn try_extract<T>(prefix: &str, figment: figment::Figment) -> Result<T, ConfigError>
+where
+    T: DeserializeOwned + Serialize + Default,
+{
+    let (config, errors) = extract_lossy(prefix, figment);
+
+    if errors.is_empty() {
+        Ok(config)
+    } else {
+        Err(ConfigError { errors })
+    }
+}
+
+// Extracts the config, replacing every invalid value with its default so all the bad keys are reported at once.
+fn extract_lossy<T>(prefix: &str, figment: figment::Figment) -> (T, Vec<ConfigKeyError>)
+where
+    T: DeserializeOwned + Serialize + Default,
+{
+    use figment::Figment;
+    use figment::providers::Serialized;
+
+    let defaults = Figment::from(Serialized::defaults(T::default()));
+    let mut figment = defaults.clone().merge(figment);
+    let mut errors = Vec::new();
+
+    loop {
+        let error = match figment.extract::<T>() {
+            Ok(config) => return (config, errors),
+            Err(error) => error,
+        };
+
+        let path = error.path.join(".");
+        let key = format!("{prefix}{}", error.path.join("_").to_uppercase());
+        let is_repeated = errors.iter().any(|e: &ConfigKeyError| e.key == key);
 
-    Figment::from(Serialized::defaults(T::default()))
-        .merge(Env::prefixed(prefix))
-        .extract()
-        .unwrap()
+        errors.push(ConfigKeyError {
+            key,
+            message: error.kind.to_string(),
+        });
+
+        match defaults.find_value(&path) {
+            Ok(value) if !path.is_empty() && !is_repeated => {
+                figment = figment.merge(Serialized::global(&path, value));
+            }
+            _ => return (T::default(), errors),
+        }
+    }
+}
+
+pub fn is_development() -> bool {
+    std::env::var("DOT_PROFILE").is_ok_and(|profile| profile == "development")
+}
+
+pub(crate) fn validate_config() -> Result<(), ConfigError> {
+    use crate::keys::SESSION_KEY_MIN_LENGTH;
+
+    let (session_config, mut errors) = extract_lossy::<SessionConfig>("SESSION_", config_figment("SESSION_"));
+    let (pagination_config, pagination_errors) =
+        extract_lossy::<PaginationConfig>("PAGINATION_", config_figment("PAGINATION_"));
+
+    errors.extend(pagination_errors);
+
+    if pagination_config.min_page_size == 0 || pagination_config.min_page_size > pagination_config.max_page_size {
+        errors.push(ConfigKeyError {
+            key: "PAGINATION_MIN_PAGE_SIZE".to_owned(),
+            message: format!(
+                "must be between 1 and PAGINATION_MAX_PAGE_SIZE ({})",
+                pagination_config.max_page_size
+            ),
+        });
+    } else if !(pagination_config.min_page_size..=pagination_config.max_page_size)
+        .contains(&pagination_config.default_page_size)
+    {
+        errors.push(ConfigKeyError {
+            key: "PAGINATION_DEFAULT_PAGE_SIZE".to_owned(),
+            message: format!(
+                "must be between {} and {}",
+                pagination_config.min_page_size, pagination_config.max_page_size
+            ),
+        });
+    }
+
+    if session_config.key.len() < SESSION_KEY_MIN_LENGTH {
+        errors.push(ConfigKeyError {
+            key: "SESSION_KEY".to_owned(),
+            message: format!(
+                "must be at least {SESSION_KEY_MIN_LENGTH} bytes long, found {} bytes",
+                session_config.key.len()
+            ),
+        });
+    } else if session_config.key == SessionConfig::default().key && !is_development() {
+        errors.push(ConfigKeyError {
+            key: "SESSION_KEY".to_owned(),
+            message: "must not be the default key outside of development, generate one with `dot generate-key` or set \
+                      DOT_PROFILE=development"
+                .to_owned(),
+        });
+    }
+
+    if session_config.store().is_none() {
+        let enabled = SessionStoreKind::ENABLED
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        errors.push(ConfigKeyError {
+            key: "SESSION_STORE".to_owned(),
+            message: format!(
+                "must be set when several session stores are enabled ({})",
+                enabled.join(", ")
+            ),
+        });
+    }
+
+    for (index, key) in session_config.previous_keys.iter().enumerate() {
+        if key.len() < SESSION_KEY_MIN_LENGTH {
+            errors.push(ConfigKeyError {
+                key: "SESSION_PREVIOUS_KEYS".to_owned(),
+                message: format!(
+                    "key {index} must be at least {SESSION_KEY_MIN_LENGTH} bytes long, found {} bytes",
+                    key.len()
+                ),
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ConfigError { errors })
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfigError {
+    pub errors: Vec<ConfigKeyError>,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid configuration")?;
+
+        for error in &self.errors {
+            write!(f, "\n  {}: {}", error.key, error.message)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Debug)]
+pub struct ConfigKeyError {
+    pub key: String,
+    pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionStoreKind {
+    File,
+    Memory,
+    Redis,
+    Sqlite,
+}
+
+impl SessionStoreKind {
+    pub(crate) const ENABLED: &[Self] = &[
+        #[cfg(feature = "session-file")]
+        Self::File,
+        #[cfg(feature = "session-memory")]
+        Self::Memory,
+        #[cfg(feature = "session-redis")]
+        Self::Redis,
+        #[cfg(feature = "session-sqlite")]
+        Self::Sqlite,
+    ];
+}
+
+impl Display for SessionStoreKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::File => "file",
+            Self::Memory => "memory",
+            Self::Redis => "redis",
+            Self::Sqlite => "sqlite",
+        })
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct PaginationConfig {
+    pub cursor_ttl: u64,
+    pub default_page_size: u8,
+    pub max_page_size: u8,
+    pub min_page_size: u8,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            cursor_ttl: 86_400,
+            default_page_size: 10,
+            max_page_size: 100,
+            min_page_size: 1,
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize)]
 pub(crate) struct SessionConfig {
     pub domain: String,
+    pub file_path: String,
     pub key: String,
     pub name: String,
+    #[serde(deserialize_with = "deserialize_comma_separated")]
+    pub previous_keys: Vec<String>,
     pub redis_url: String,
     pub secure: bool,
+    pub sqlite_url: String,
+    pub store: Option<SessionStoreKind>,
+}
+
+impl SessionConfig {
+    // Features are unified across the dependency graph, so the backend is only implied when a single one is enabled.
+    pub fn store(&self) -> Option<SessionStoreKind> {
+        match (self.store, SessionStoreKind::ENABLED) {
+            (Some(store), _) => Some(store),
+            (None, [store]) => Some(*store),
+            (None, _) => None,
+        }
+    }
 }
 
 impl Default for SessionConfig {
     fn default() -> Self {
         Self {
             domain: "".to_owned(),
+            file_path: ".sessions".to_owned(),
             key: "abcdefghijklmnopqrestuvvwxyz0123456789ABCDEFGHIJKLMNOPQRESTUVVWX".to_owned(),
             name: "_session".to_owned(),
+            previous_keys: Vec::new(),
             redis_url: "redis://127.0.0.1:6379/0".to_owned(),
             secure: false,
+            sqlite_url: "sqlite://sessions.db?mode=rwc".to_owned(),
+            store: None,
         }
     }
 }
+
+fn deserialize_comma_separated<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum CommaSeparated {
+        String(String),
+        Vec(Vec<String>),
+    }
+
+    Ok(match CommaSeparated::deserialize(deserializer)? {
+        CommaSeparated::String(value) => value
+            .split(',')
+            .map(|item| item.trim().to_owned())
+            .filter(|item| !item.is_empty())
+            .collect(),
+        CommaSeparated::Vec(values) => values,
+    })
+}
+
+// `Jail` closures return `figment::Error`, which is large but only ever built when a test fails.
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod tests {
+    use figment::Jail;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct AppConfig {
+        name: String,
+        port: u16,
+        secure: bool,
+    }
+
+    impl Default for AppConfig {
+        fn default() -> Self {
+            Self {
+                name: "app".to_owned(),
+                port: 3000,
+                secure: false,
+            }
+        }
+    }
+
+    fn error_keys(error: ConfigError) -> Vec<String> {
+        error.errors.into_iter().map(|error| error.key).collect()
+    }
+
+    #[test]
+    fn reports_every_invalid_env_var() {
+        Jail::expect_with(|jail| {
+            jail.set_env("APP_NAME", "dot");
+            jail.set_env("APP_PORT", "abc");
+            jail.set_env("APP_SECURE", "yes");
+
+            let (config, errors) = extract_lossy::<AppConfig>(
+                "APP_",
+                figment::Figment::from(figment::providers::Env::prefixed("APP_")),
+            );
+
+            assert_eq!(
+                config,
+                AppConfig {
+                    name: "dot".to_owned(),
+                    ..Default::default()
+                }
+            );
+            assert_eq!(
+                errors.into_iter().map(|error| error.key).collect::<Vec<_>>(),
+                ["APP_PORT", "APP_SECURE"]
+            );
+            assert_eq!(
+                error_keys(try_extract_from_env::<AppConfig>("APP_").unwrap_err()),
+                ["APP_PORT", "APP_SECURE"]
+            );
+
+            jail.set_env("APP_PORT", "8080");
+            jail.set_env("APP_SECURE", "true");
+
+            assert_eq!(
+                try_extract_from_env::<AppConfig>("APP_").unwrap(),
+                AppConfig {
+                    name: "dot".to_owned(),
+                    port: 8080,
+                    secure: true,
+                }
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn reports_invalid_values_from_the_config_file() {
+        Jail::expect_with(|jail| {
+            jail.create_file("dot.toml", "[default.app]\nport = \"abc\"\nsecure = true\n")?;
+
+            assert_eq!(
+                error_keys(try_extract_config::<AppConfig>("APP_").unwrap_err()),
+                ["APP_PORT"]
+            );
+
+            jail.create_file("custom.toml", "[default.app]\nport = 70000\n")?;
+            jail.set_env("DOT_CONFIG", "custom.toml");
+
+            assert_eq!(
+                error_keys(try_extract_config::<AppConfig>("APP_").unwrap_err()),
+                ["APP_PORT"]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn layers_profiles_and_env_vars() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "dot.toml",
+                r#"
+                [default.app]
+                name = "default"
+                port = 1000
+
+                [production.app]
+                port = 2000
+
+                [other.app]
+                port = 3000
+                "#,
+            )?;
+
+            assert_eq!(
+                try_extract_config::<AppConfig>("APP_").unwrap(),
+                AppConfig {
+                    name: "default".to_owned(),
+                    port: 1000,
+                    secure: false,
+                }
+            );
+
+            jail.set_env("DOT_PROFILE", "production");
+
+            assert_eq!(
+                try_extract_config::<AppConfig>("APP_").unwrap(),
+                AppConfig {
+                    name: "default".to_owned(),
+                    port: 2000,
+                    secure: false,
+                }
+            );
+
+            jail.set_env("APP_PORT", "4000");
+            jail.set_env("APP_SECURE", "true");
+
+            assert_eq!(
+                try_extract_config::<AppConfig>("APP_").unwrap(),
+                AppConfig {
+                    name: "default".to_owned(),
+                    port: 4000,
+                    secure: true,
+                }
+            );
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,520 @@
+use fluent_templates::LanguageIdentifier;
+use time::{Date, OffsetDateTime, Time};
+use time_tz::{OffsetDateTimeExt, Tz};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DateStyle {
+    Short,
+    #[default]
+    Medium,
+    Long,
+}
+
+struct LocaleData {
+    currency: &'static str,
+    date_long: &'static str,
+    date_medium: &'static str,
+    date_short: &'static str,
+    datetime: &'static str,
+    day_periods: [&'static str; 2],
+    decimal: &'static str,
+    digits: Option<[char; 10]>,
+    group: &'static str,
+    months: [&'static str; 12],
+    months_short: [&'static str; 12],
+    percent: &'static str,
+    time: &'static str,
+}
+
+const EN: LocaleData = LocaleData {
+    currency: "{symbol}{number}",
+    date_long: "{MMMM} {d}, {y}",
+    date_medium: "{MMM} {d}, {y}",
+    date_short: "{m}/{d}/{y}",
+    datetime: "{date}, {time}",
+    day_periods: ["AM", "PM"],
+    decimal: ".",
+    digits: None,
+    group: ",",
+    months: [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ],
+    months_short: [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ],
+    percent: "{number}%",
+    time: "{h}:{MM}\u{202f}{a}",
+};
+
+const AR_MONTHS: [&str; 12] = [
+    "يناير",
+    "فبراير",
+    "مارس",
+    "أبريل",
+    "مايو",
+    "يونيو",
+    "يوليو",
+    "أغسطس",
+    "سبتمبر",
+    "أكتوبر",
+    "نوفمبر",
+    "ديسمبر",
+];
+
+const AR: LocaleData = LocaleData {
+    currency: "{number} {symbol}",
+    date_long: "{d} {MMMM} {y}",
+    date_medium: "{dd}\u{200f}/{mm}\u{200f}/{y}",
+    date_short: "{d}\u{200f}/{m}\u{200f}/{y}",
+    datetime: "{date}، {time}",
+    day_periods: ["ص", "م"],
+    decimal: "٫",
+    digits: Some(['٠', '١', '٢', '٣', '٤', '٥', '٦', '٧', '٨', '٩']),
+    group: "٬",
+    months: AR_MONTHS,
+    months_short: AR_MONTHS,
+    percent: "{number}٪؜",
+    time: "{h}:{MM}\u{a0}{a}",
+};
+
+const DE: LocaleData = LocaleData {
+    currency: "{number}\u{a0}{symbol}",
+    date_long: "{d}. {MMMM} {y}",
+    date_medium: "{dd}.{mm}.{y}",
+    date_short: "{dd}.{mm}.{y}",
+    datetime: "{date}, {time}",
+    day_periods: ["", ""],
+    decimal: ",",
+    digits: None,
+    group: ".",
+    months: [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ],
+    months_short: [
+        "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.", "Dez.",
+    ],
+    percent: "{number}\u{a0}%",
+    time: "{HH}:{MM}",
+};
+
+const ES: LocaleData = LocaleData {
+    currency: "{number}\u{a0}{symbol}",
+    date_long: "{d} de {MMMM} de {y}",
+    date_medium: "{d} {MMM} {y}",
+    date_short: "{d}/{m}/{y}",
+    datetime: "{date}, {time}",
+    day_periods: ["", ""],
+    decimal: ",",
+    digits: None,
+    group: ".",
+    months: [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ],
+    months_short: [
+        "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+    ],
+    percent: "{number}\u{a0}%",
+    time: "{H}:{MM}",
+};
+
+const FR: LocaleData = LocaleData {
+    currency: "{number}\u{a0}{symbol}",
+    date_long: "{d} {MMMM} {y}",
+    date_medium: "{d} {MMM} {y}",
+    date_short: "{dd}/{mm}/{y}",
+    datetime: "{date} {time}",
+    day_periods: ["", ""],
+    decimal: ",",
+    digits: None,
+    group: "\u{202f}",
+    months: [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ],
+    months_short: [
+        "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc.",
+    ],
+    percent: "{number}\u{a0}%",
+    time: "{HH}:{MM}",
+};
+
+const PT: LocaleData = LocaleData {
+    currency: "{symbol}\u{a0}{number}",
+    date_long: "{d} de {MMMM} de {y}",
+    date_medium: "{d} de {MMM} de {y}",
+    date_short: "{dd}/{mm}/{y}",
+    datetime: "{date}, {time}",
+    day_periods: ["", ""],
+    decimal: ",",
+    digits: None,
+    group: ".",
+    months: [
+        "janeiro",
+        "fevereiro",
+        "março",
+        "abril",
+        "maio",
+        "junho",
+        "julho",
+        "agosto",
+        "setembro",
+        "outubro",
+        "novembro",
+        "dezembro",
+    ],
+    months_short: [
+        "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.", "nov.", "dez.",
+    ],
+    percent: "{number}%",
+    time: "{HH}:{MM}",
+};
+
+fn locale_data(language: &LanguageIdentifier) -> &'static LocaleData {
+    match language.language.as_str() {
+        "ar" => &AR,
+        "de" => &DE,
+        "es" => &ES,
+        "fr" => &FR,
+        "pt" => &PT,
+        _ => &EN,
+    }
+}
+
+fn localize_digits(data: &LocaleData, value: String) -> String {
+    match data.digits {
+        Some(digits) => value
+            .chars()
+            .map(|c| c.to_digit(10).map_or(c, |digit| digits[digit as usize]))
+            .collect(),
+        None => value,
+    }
+}
+
+fn format_decimal(data: &LocaleData, value: f64, fraction_digits: usize) -> String {
+    let formatted = format!("{:.*}", fraction_digits, value.abs());
+    let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+    let mut grouped = String::new();
+
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push_str(data.group);
+        }
+
+        grouped.push(digit);
+    }
+
+    if !fraction.is_empty() {
+        grouped.push_str(data.decimal);
+        grouped.push_str(fraction);
+    }
+
+    if is_negative(value, fraction_digits) {
+        grouped.insert(0, '-');
+    }
+
+    localize_digits(data, grouped)
+}
+
+// Whether the value is still negative once rounded, so `-0.001` isn't shown as `-0.00`.
+fn is_negative(value: f64, fraction_digits: usize) -> bool {
+    value < 0.0
+        && format!("{:.*}", fraction_digits, value.abs())
+            .bytes()
+            .any(|byte| byte.is_ascii_digit() && byte != b'0')
+}
+
+fn currency_symbol(currency: &str) -> &str {
+    match currency {
+        "BRL" => "R$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" => "¥",
+        "USD" => "$",
+        _ => currency,
+    }
+}
+
+fn currency_fraction_digits(currency: &str) -> usize {
+    match currency {
+        "CLP" | "JPY" | "KRW" | "PYG" | "VND" => 0,
+        _ => 2,
+    }
+}
+
+pub fn format_currency(language: &LanguageIdentifier, amount: f64, currency: &str) -> String {
+    let data = locale_data(language);
+    let fraction_digits = currency_fraction_digits(currency);
+    let formatted = data
+        .currency
+        .replace("{symbol}", currency_symbol(currency))
+        .replace("{number}", &format_decimal(data, amount.abs(), fraction_digits));
+
+    if is_negative(amount, fraction_digits) {
+        format!("-{formatted}")
+    } else {
+        formatted
+    }
+}
+
+pub fn format_date(language: &LanguageIdentifier, date: Date, style: DateStyle) -> String {
+    let data = locale_data(language);
+    let pattern = match style {
+        DateStyle::Short => data.date_short,
+        DateStyle::Medium => data.date_medium,
+        DateStyle::Long => data.date_long,
+    };
+    let month = date.month() as usize - 1;
+
+    let formatted = pattern
+        .replace("{dd}", &format!("{:02}", date.day()))
+        .replace("{d}", &date.day().to_string())
+        .replace("{mm}", &format!("{:02}", month + 1))
+        .replace("{m}", &(month + 1).to_string())
+        .replace("{y}", &date.year().to_string());
+
+    localize_digits(data, formatted)
+        .replace("{MMMM}", data.months[month])
+        .replace("{MMM}", data.months_short[month])
+}
+
+pub fn format_number(language: &LanguageIdentifier, value: f64, fraction_digits: usize) -> String {
+    format_decimal(locale_data(language), value, fraction_digits)
+}
+
+pub fn format_percent(language: &LanguageIdentifier, value: f64, fraction_digits: usize) -> String {
+    let data = locale_data(language);
+
+    data.percent
+        .replace("{number}", &format_decimal(data, value * 100.0, fraction_digits))
+}
+
+pub fn format_datetime(
+    language: &LanguageIdentifier,
+    datetime: OffsetDateTime,
+    timezone: &Tz,
+    style: DateStyle,
+) -> String {
+    let datetime = datetime.to_timezone(timezone);
+
+    locale_data(language)
+        .datetime
+        .replace("{date}", &format_date(language, datetime.date(), style))
+        .replace("{time}", &format_time(language, datetime.time()))
+}
+
+pub fn format_time(language: &LanguageIdentifier, time: Time) -> String {
+    let data = locale_data(language);
+    let hour12 = match time.hour() % 12 {
+        0 => 12,
+        hour => hour,
+    };
+
+    let formatted = data
+        .time
+        .replace("{HH}", &format!("{:02}", time.hour()))
+        .replace("{H}", &time.hour().to_string())
+        .replace("{h}", &hour12.to_string())
+        .replace("{MM}", &format!("{:02}", time.minute()));
+
+    localize_digits(data, formatted).replace("{a}", data.day_periods[usize::from(time.hour() >= 12)])
+}
+
+#[cfg(test)]
+mod tests {
+    use fluent_templates::LanguageIdentifier;
+    use time::macros::{date, datetime, time};
+    use time_tz::timezones;
+
+    use super::*;
+
+    fn language(id: &str) -> LanguageIdentifier {
+        id.parse().unwrap()
+    }
+
+    #[test]
+    fn formats_numbers_per_locale() {
+        for (id, expected) in [
+            ("en", "1,234,567.89"),
+            ("ar", "١٬٢٣٤٬٥٦٧٫٨٩"),
+            ("de", "1.234.567,89"),
+            ("es", "1.234.567,89"),
+            ("fr", "1\u{202f}234\u{202f}567,89"),
+            ("pt", "1.234.567,89"),
+            ("ja", "1,234,567.89"),
+        ] {
+            assert_eq!(format_number(&language(id), 1_234_567.891, 2), expected, "{id}");
+        }
+
+        assert_eq!(format_number(&language("en"), 999.999, 2), "1,000.00");
+        assert_eq!(format_number(&language("en"), -1234.6, 0), "-1,235");
+        assert_eq!(format_number(&language("en"), 0.0, 0), "0");
+    }
+
+    #[test]
+    fn drops_the_sign_of_negative_values_rounding_to_zero() {
+        let en = language("en");
+
+        assert_eq!(format_number(&en, -0.001, 2), "0.00");
+        assert_eq!(format_number(&en, -0.4, 0), "0");
+        assert_eq!(format_number(&en, -0.005, 3), "-0.005");
+        assert_eq!(format_currency(&en, -0.004, "USD"), "$0.00");
+        assert_eq!(format_currency(&en, -0.4, "JPY"), "¥0");
+        assert_eq!(format_percent(&en, -0.00001, 1), "0.0%");
+    }
+
+    #[test]
+    fn formats_currencies_per_locale() {
+        for (id, currency, expected) in [
+            ("en", "USD", "$1,234.50"),
+            ("ar", "USD", "١٬٢٣٤٫٥٠ $"),
+            ("de", "EUR", "1.234,50\u{a0}€"),
+            ("es", "EUR", "1.234,50\u{a0}€"),
+            ("fr", "GBP", "1\u{202f}234,50\u{a0}£"),
+            ("pt", "BRL", "R$\u{a0}1.234,50"),
+            ("en", "CHF", "CHF1,234.50"),
+        ] {
+            assert_eq!(
+                format_currency(&language(id), 1234.5, currency),
+                expected,
+                "{id} {currency}"
+            );
+        }
+
+        assert_eq!(format_currency(&language("en"), -5.5, "USD"), "-$5.50");
+        assert_eq!(format_currency(&language("de"), -5.5, "EUR"), "-5,50\u{a0}€");
+    }
+
+    #[test]
+    fn formats_currencies_without_fraction_digits() {
+        assert_eq!(format_currency(&language("en"), 1234.6, "JPY"), "¥1,235");
+        assert_eq!(format_currency(&language("es"), 1234.4, "CLP"), "1.234\u{a0}CLP");
+        assert_eq!(format_currency(&language("ar"), 1234.0, "KRW"), "١٬٢٣٤ KRW");
+    }
+
+    #[test]
+    fn formats_percentages_per_locale() {
+        for (id, expected) in [
+            ("en", "25.6%"),
+            ("ar", "٢٥٫٦٪؜"),
+            ("de", "25,6\u{a0}%"),
+            ("fr", "25,6\u{a0}%"),
+            ("pt", "25,6%"),
+        ] {
+            assert_eq!(format_percent(&language(id), 0.256, 1), expected, "{id}");
+        }
+    }
+
+    #[test]
+    fn formats_dates_per_locale_and_style() {
+        let date = date!(2025 - 03 - 07);
+
+        for (id, short, medium, long) in [
+            ("en", "3/7/2025", "Mar 7, 2025", "March 7, 2025"),
+            (
+                "ar",
+                "٧\u{200f}/٣\u{200f}/٢٠٢٥",
+                "٠٧\u{200f}/٠٣\u{200f}/٢٠٢٥",
+                "٧ مارس ٢٠٢٥",
+            ),
+            ("de", "07.03.2025", "07.03.2025", "7. März 2025"),
+            ("es", "7/3/2025", "7 mar 2025", "7 de marzo de 2025"),
+            ("fr", "07/03/2025", "7 mars 2025", "7 mars 2025"),
+            ("pt", "07/03/2025", "7 de mar. de 2025", "7 de março de 2025"),
+        ] {
+            let language = language(id);
+
+            assert_eq!(format_date(&language, date, DateStyle::Short), short, "{id}");
+            assert_eq!(format_date(&language, date, DateStyle::Medium), medium, "{id}");
+            assert_eq!(format_date(&language, date, DateStyle::Long), long, "{id}");
+        }
+    }
+
+    #[test]
+    fn formats_times_with_12_and_24_hour_clocks() {
+        for (id, midnight, afternoon) in [
+            ("en", "12:05\u{202f}AM", "1:30\u{202f}PM"),
+            ("ar", "١٢:٠٥\u{a0}ص", "١:٣٠\u{a0}م"),
+            ("de", "00:05", "13:30"),
+            ("es", "0:05", "13:30"),
+            ("fr", "00:05", "13:30"),
+            ("pt", "00:05", "13:30"),
+        ] {
+            let language = language(id);
+
+            assert_eq!(format_time(&language, time!(0:05)), midnight, "{id}");
+            assert_eq!(format_time(&language, time!(13:30)), afternoon, "{id}");
+        }
+
+        assert_eq!(format_time(&language("en"), time!(12:00)), "12:00\u{202f}PM");
+    }
+
+    #[test]
+    fn formats_datetimes_in_the_given_timezone() {
+        let datetime = datetime!(2025-03-08 02:30 UTC);
+
+        assert_eq!(
+            format_datetime(&language("en"), datetime, timezones::db::UTC, DateStyle::Medium),
+            "Mar 8, 2025, 2:30\u{202f}AM"
+        );
+        assert_eq!(
+            format_datetime(
+                &language("en"),
+                datetime,
+                timezones::db::america::NEW_YORK,
+                DateStyle::Medium
+            ),
+            "Mar 7, 2025, 9:30\u{202f}PM"
+        );
+        assert_eq!(
+            format_datetime(&language("fr"), datetime, timezones::db::europe::PARIS, DateStyle::Long),
+            "8 mars 2025 03:30"
+        );
+        assert_eq!(
+            format_datetime(&language("ar"), datetime, timezones::db::asia::RIYADH, DateStyle::Short),
+            "٨\u{200f}/٣\u{200f}/٢٠٢٥، ٥:٣٠\u{a0}ص"
+        );
+    }
+}
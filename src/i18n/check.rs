@@ -0,0 +1,318 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use fluent_syntax::ast::{Entry, Expression, InlineExpression, Pattern, PatternElement};
+
+use super::{Usage, collect_usages};
+
+const DOT_LOCALES: [(&str, &str); 6] = [
+    ("ar", include_str!("../../locales/ar/default.ftl")),
+    ("de", include_str!("../../locales/de/default.ftl")),
+    ("en", include_str!("../../locales/en/default.ftl")),
+    ("es", include_str!("../../locales/es/default.ftl")),
+    ("fr", include_str!("../../locales/fr/default.ftl")),
+    ("pt", include_str!("../../locales/pt/default.ftl")),
+];
+
+type Messages = BTreeMap<String, BTreeSet<String>>;
+
+pub enum Problem {
+    Missing {
+        key: String,
+        locale: String,
+        location: String,
+    },
+    Unbalanced {
+        key: String,
+        locale: String,
+        location: String,
+        expected: BTreeSet<String>,
+        found: BTreeSet<String>,
+    },
+    Unused {
+        key: String,
+        locale: String,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |arguments: &BTreeSet<String>| {
+            arguments
+                .iter()
+                .map(|argument| format!("${argument}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        match self {
+            Self::Missing { key, locale, location } => write!(f, "{location}: `{key}` is missing in {locale}"),
+            Self::Unbalanced {
+                key,
+                locale,
+                location,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{location}: `{key}` is called with [{}] but {locale} uses [{}]",
+                join(found),
+                join(expected)
+            ),
+            Self::Unused { key, locale } => write!(f, "`{key}` is never used but is defined in {locale}"),
+        }
+    }
+}
+
+pub fn check(sources: &Path, locales: &Path) -> io::Result<Vec<Problem>> {
+    let mut usages = BTreeMap::new();
+
+    collect_usages(sources, &mut usages)?;
+
+    let app_locales = read_locales(locales)?;
+    let shipped_locales = match read_languages(locales)? {
+        Some(languages) => languages,
+        None if !app_locales.is_empty() => app_locales.keys().cloned().collect(),
+        None => DOT_LOCALES.iter().map(|(locale, _)| locale.to_string()).collect(),
+    };
+
+    Ok(find_problems(&usages, &app_locales, &shipped_locales))
+}
+
+// Only the locales shipped by the app are checked, dot's translations are just a fallback for its keys.
+fn find_problems(
+    usages: &BTreeMap<String, Vec<Usage>>,
+    app_locales: &BTreeMap<String, Messages>,
+    shipped_locales: &BTreeSet<String>,
+) -> Vec<Problem> {
+    let dot_locales = DOT_LOCALES
+        .iter()
+        .map(|(locale, source)| (locale.to_string(), parse_messages(source)))
+        .collect::<BTreeMap<_, _>>();
+    let dot_keys = dot_locales
+        .values()
+        .flat_map(|messages| messages.keys())
+        .collect::<BTreeSet<_>>();
+
+    let mut problems = Vec::new();
+
+    for (locale, messages) in app_locales {
+        for key in messages.keys() {
+            if !usages.contains_key(key) && !dot_keys.contains(key) {
+                problems.push(Problem::Unused {
+                    key: key.clone(),
+                    locale: locale.clone(),
+                });
+            }
+        }
+    }
+
+    for (key, key_usages) in usages {
+        for locale in shipped_locales {
+            let message = app_locales
+                .get(locale)
+                .and_then(|messages| messages.get(key))
+                .or_else(|| dot_locales.get(locale).and_then(|messages| messages.get(key)));
+
+            let Some(expected) = message else {
+                problems.push(Problem::Missing {
+                    key: key.clone(),
+                    locale: locale.clone(),
+                    location: key_usages[0].location.clone(),
+                });
+                continue;
+            };
+
+            for usage in key_usages.iter().filter(|usage| usage.arguments != *expected) {
+                problems.push(Problem::Unbalanced {
+                    key: key.clone(),
+                    locale: locale.clone(),
+                    location: usage.location.clone(),
+                    expected: expected.clone(),
+                    found: usage.arguments.clone(),
+                });
+            }
+        }
+    }
+
+    problems
+}
+
+fn read_languages(dir: &Path) -> io::Result<Option<BTreeSet<String>>> {
+    let path = dir.join("languages.yaml");
+
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let languages = serde_yaml::from_str::<Vec<Vec<String>>>(&fs::read_to_string(path)?).map_err(io::Error::other)?;
+
+    Ok(Some(
+        languages
+            .into_iter()
+            .filter_map(|language| language.into_iter().next())
+            .collect(),
+    ))
+}
+
+fn read_locales(dir: &Path) -> io::Result<BTreeMap<String, Messages>> {
+    let mut locales = BTreeMap::new();
+
+    if !dir.is_dir() {
+        return Ok(locales);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if !path.is_dir() {
+            continue;
+        }
+
+        let mut messages = Messages::new();
+
+        for file in fs::read_dir(&path)? {
+            let file = file?.path();
+
+            if file.extension().is_some_and(|extension| extension == "ftl") {
+                messages.extend(parse_messages(&fs::read_to_string(file)?));
+            }
+        }
+
+        locales.insert(path.file_name().unwrap().to_string_lossy().into_owned(), messages);
+    }
+
+    Ok(locales)
+}
+
+fn parse_messages(source: &str) -> Messages {
+    let resource = fluent_syntax::parser::parse(source).unwrap_or_else(|(resource, _)| resource);
+
+    resource
+        .body
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Entry::Message(message) => {
+                let mut variables = BTreeSet::new();
+
+                if let Some(value) = &message.value {
+                    pattern_variables(value, &mut variables);
+                }
+
+                Some((message.id.name.to_owned(), variables))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn pattern_variables(pattern: &Pattern<&str>, variables: &mut BTreeSet<String>) {
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            expression_variables(expression, variables);
+        }
+    }
+}
+
+fn expression_variables(expression: &Expression<&str>, variables: &mut BTreeSet<String>) {
+    match expression {
+        Expression::Select { selector, variants } => {
+            inline_expression_variables(selector, variables);
+
+            for variant in variants {
+                pattern_variables(&variant.value, variables);
+            }
+        }
+        Expression::Inline(expression) => inline_expression_variables(expression, variables),
+    }
+}
+
+fn inline_expression_variables(expression: &InlineExpression<&str>, variables: &mut BTreeSet<String>) {
+    match expression {
+        InlineExpression::FunctionReference { arguments, .. } => {
+            for argument in arguments
+                .positional
+                .iter()
+                .chain(arguments.named.iter().map(|argument| &argument.value))
+            {
+                inline_expression_variables(argument, variables);
+            }
+        }
+        InlineExpression::VariableReference { id } => {
+            variables.insert(id.name.to_owned());
+        }
+        InlineExpression::Placeable { expression } => expression_variables(expression, variables),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::scan_usages;
+
+    fn problems(source: &str, app_locales: &[(&str, &str)], shipped_locales: &[&str]) -> Vec<String> {
+        let mut usages = BTreeMap::new();
+
+        scan_usages(Path::new("app.rs"), source, &mut usages);
+
+        let app_locales = app_locales
+            .iter()
+            .map(|(locale, source)| (locale.to_string(), parse_messages(source)))
+            .collect();
+        let shipped_locales = shipped_locales.iter().map(|locale| locale.to_string()).collect();
+
+        find_problems(&usages, &app_locales, &shipped_locales)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn reports_keys_missing_in_shipped_locales() {
+        let source = r#"tr!("title"); tr!("cancel");"#;
+
+        assert_eq!(
+            problems(source, &[("en", "title = Title"), ("de", "")], &["en", "de"]),
+            ["app.rs:1: `title` is missing in de"]
+        );
+        assert_eq!(
+            problems(source, &[("en", "title = Title")], &["en", "nl"]),
+            [
+                "app.rs:1: `cancel` is missing in nl",
+                "app.rs:1: `title` is missing in nl"
+            ]
+        );
+        assert!(problems(source, &[("en", "title = Title")], &["en"]).is_empty());
+    }
+
+    #[test]
+    fn reports_unused_keys() {
+        assert_eq!(
+            problems(
+                r#"tr!("title")"#,
+                &[("en", "title = Title\nold = Old\ncancel = Cancel")],
+                &["en"]
+            ),
+            ["`old` is never used but is defined in en"]
+        );
+    }
+
+    #[test]
+    fn reports_unbalanced_arguments() {
+        let locales = [("en", "greeting = Hello { $name }")];
+
+        assert_eq!(
+            problems(r#"tr!("greeting")"#, &locales, &["en"]),
+            ["app.rs:1: `greeting` is called with [] but en uses [$name]"]
+        );
+        assert_eq!(
+            problems(r#"tr!("greeting", { "user" => user })"#, &locales, &["en"]),
+            ["app.rs:1: `greeting` is called with [$user] but en uses [$name]"]
+        );
+        assert!(problems(r#"tr!("greeting", { "name" => name })"#, &locales, &["en"]).is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[cfg(feature = "cli")]
+mod check;
+
+#[cfg(feature = "cli")]
+pub use check::{Problem, check};
+
+#[cfg_attr(not(feature = "cli"), allow(dead_code))]
+struct Usage {
+    arguments: BTreeSet<String>,
+    location: String,
+}
+
+fn collect_usages(path: &Path, usages: &mut BTreeMap<String, Vec<Usage>>) -> io::Result<()> {
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            collect_usages(&entry?.path(), usages)?;
+        }
+    } else if path.extension().is_some_and(|extension| extension == "rs") {
+        scan_usages(path, &fs::read_to_string(path)?, usages);
+    }
+
+    Ok(())
+}
+
+pub fn used_keys(sources: &Path) -> io::Result<BTreeSet<String>> {
+    let mut usages = BTreeMap::new();
+
+    collect_usages(sources, &mut usages)?;
+
+    Ok(usages.into_keys().collect())
+}
+
+fn scan_usages(path: &Path, source: &str, usages: &mut BTreeMap<String, Vec<Usage>>) {
+    for (index, _) in source.match_indices("tr!(") {
+        let before = &source[..index];
+
+        if !before.ends_with("move_") && before.ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == '"') {
+            continue;
+        }
+
+        if let Some((key, arguments)) = parse_macro_call(&source[index + 4..]) {
+            usages.entry(key).or_default().push(Usage {
+                arguments,
+                location: location(path, before),
+            });
+        }
+    }
+}
+
+// Parses `"key"`, `i18n, "key"` and either of them followed by `, { "name" => value, ... }`.
+fn parse_macro_call(call: &str) -> Option<(String, BTreeSet<String>)> {
+    let call = call.trim_start();
+    let call = match call.split_once(',') {
+        Some((i18n, rest))
+            if !call.starts_with('"') && i18n.trim().chars().all(|c| c.is_alphanumeric() || c == '_') =>
+        {
+            rest.trim_start()
+        }
+        _ => call,
+    };
+
+    let (key, rest) = call.strip_prefix('"')?.split_once('"')?;
+
+    if !key.starts_with(|c: char| c.is_ascii_alphabetic())
+        || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return None;
+    }
+    let mut arguments = BTreeSet::new();
+
+    if let Some(rest) = rest.trim_start().strip_prefix(',')
+        && let Some(rest) = rest.trim_start().strip_prefix('{')
+    {
+        let mut depth = 1;
+        let end = rest
+            .char_indices()
+            .find(|(_, c)| {
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+
+                depth == 0
+            })
+            .map_or(rest.len(), |(index, _)| index);
+
+        for (index, _) in rest[..end].match_indices("=>") {
+            if let Some(name) = rest[..index].trim_end().strip_suffix('"')
+                && let Some(start) = name.rfind('"')
+            {
+                arguments.insert(name[start + 1..].to_owned());
+            }
+        }
+    }
+
+    Some((key.to_owned(), arguments))
+}
+
+fn location(path: &Path, before: &str) -> String {
+    format!("{}:{}", path.display(), before.matches('\n').count() + 1)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arguments<const N: usize>(names: [&str; N]) -> BTreeSet<String> {
+        names.into_iter().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn parses_macro_calls() {
+        for (call, expected) in [
+            (r#""hello")"#, Some(("hello", arguments([])))),
+            (r#" i18n, "hello-world")"#, Some(("hello-world", arguments([])))),
+            (
+                r#""greeting", { "name" => name, "count" => { count + 1 } })"#,
+                Some(("greeting", arguments(["count", "name"]))),
+            ),
+            (
+                r#"i18n, "greeting", {"name" => user.name()})"#,
+                Some(("greeting", arguments(["name"]))),
+            ),
+            (r#"key)"#, None),
+            (r#""1st")"#, None),
+            (r#""with space")"#, None),
+        ] {
+            let parsed = parse_macro_call(call);
+
+            assert_eq!(
+                parsed.as_ref().map(|(key, arguments)| (key.as_str(), arguments)),
+                expected.as_ref().map(|(key, arguments)| (*key, arguments)),
+                "{call}"
+            );
+        }
+    }
+
+    #[test]
+    fn scans_tr_and_move_tr_calls_only() {
+        let mut usages = BTreeMap::new();
+
+        scan_usages(
+            Path::new("app.rs"),
+            "tr!(\"a\");\nmove_tr!(\"b\");\nnot_tr!(\"c\");\n\"tr!(\"d\")",
+            &mut usages,
+        );
+
+        assert_eq!(usages.keys().collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(usages["b"][0].location, "app.rs:2");
+    }
+}
@@ -0,0 +1,12 @@
+use rand::Rng;
+use rand::distr::Alphanumeric;
+
+pub const SESSION_KEY_MIN_LENGTH: usize = 64;
+
+pub fn generate_session_key() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(SESSION_KEY_MIN_LENGTH)
+        .map(char::from)
+        .collect()
+}
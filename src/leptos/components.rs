@@ -1,6 +1,23 @@
+use std::future::Future;
+
+use leptos::either::{Either, EitherOf3};
 use leptos::ev::MouseEvent;
+use leptos::html::Div;
 use leptos::prelude::*;
+use leptos::server_fn::ServerFn;
 use leptos_fluent::tr;
+use leptos_router::hooks::use_location;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime};
+
+use crate::format::{DateStyle, format_currency, format_date, format_datetime, format_number, format_percent};
+use crate::pagination::{Cursor, CursorPage};
+
+use super::routing::{localize_path, split_path};
+use super::server_functions::SetLanguage;
+use super::{use_i18n, use_timezone};
 
 #[component]
 pub fn ConfirmationModal(
@@ -37,6 +54,327 @@ pub fn ConfirmationModal(
     }
 }
 
+#[component]
+pub fn FormattedCurrency(#[prop(into)] amount: Signal<f64>, #[prop(into)] currency: Signal<String>) -> impl IntoView {
+    let i18n = use_i18n();
+
+    view! {
+        <data value=move || amount.get().to_string()>
+            {move || format_currency(i18n.language.get().id, amount.get(), &currency.get())}
+        </data>
+    }
+}
+
+#[component]
+pub fn FormattedDate(#[prop(into)] date: Signal<Date>, #[prop(into, optional)] style: DateStyle) -> impl IntoView {
+    let i18n = use_i18n();
+
+    view! {
+        <time datetime=move || date.get().to_string()>
+            {move || format_date(i18n.language.get().id, date.get(), style)}
+        </time>
+    }
+}
+
+#[component]
+pub fn FormattedDateTime(
+    #[prop(into)] datetime: Signal<OffsetDateTime>,
+    #[prop(into, optional)] style: DateStyle,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let timezone = use_timezone();
+
+    view! {
+        <time datetime=move || datetime.get().format(&Rfc3339).ok()>
+            {move || format_datetime(i18n.language.get().id, datetime.get(), timezone.get(), style)}
+        </time>
+    }
+}
+
+#[component]
+pub fn FormattedNumber(
+    #[prop(into)] value: Signal<f64>,
+    #[prop(into, optional)] fraction_digits: usize,
+) -> impl IntoView {
+    let i18n = use_i18n();
+
+    view! {
+        <data value=move || value.get().to_string()>
+            {move || format_number(i18n.language.get().id, value.get(), fraction_digits)}
+        </data>
+    }
+}
+
+#[component]
+pub fn FormattedPercent(
+    #[prop(into)] value: Signal<f64>,
+    #[prop(into, optional)] fraction_digits: usize,
+) -> impl IntoView {
+    let i18n = use_i18n();
+
+    view! {
+        <data value=move || value.get().to_string()>
+            {move || format_percent(i18n.language.get().id, value.get(), fraction_digits)}
+        </data>
+    }
+}
+
+// `fetch_page` is usually a server function taking the `after` cursor. The first page is rendered on the server and the
+// next ones are appended whenever the sentinel after the last node comes into view.
+#[component]
+pub fn InfiniteList<T, F, Fut, CF, IV>(
+    fetch_page: F,
+    children: CF,
+    #[prop(into, optional)] class: &'static str,
+    #[prop(into, optional)] empty: Option<ViewFn>,
+) -> impl IntoView
+where
+    T: Clone + DeserializeOwned + Serialize + Send + Sync + 'static,
+    F: Fn(Option<Cursor>) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<CursorPage<T>, ServerFnError>> + Send + 'static,
+    CF: Fn(T) -> IV + Clone + Send + Sync + 'static,
+    IV: IntoView + 'static,
+{
+    let first_page = Resource::new(|| (), {
+        let fetch_page = fetch_page.clone();
+
+        move |_| fetch_page(None)
+    });
+    let next_pages = RwSignal::new(Vec::<CursorPage<T>>::new());
+    let load_next_page = Action::new(move |end_cursor: &Cursor| {
+        let page = fetch_page(Some(end_cursor.clone()));
+
+        async move {
+            let page = page.await?;
+
+            next_pages.update(|pages| pages.push(page));
+
+            Ok::<_, ServerFnError>(())
+        }
+    });
+
+    let next_cursor = move || {
+        next_pages
+            .with(|pages| pages.last().map(|page| (page.has_next_page, page.end_cursor.clone())))
+            .or_else(|| {
+                first_page
+                    .get()
+                    .and_then(Result::ok)
+                    .map(|page| (page.has_next_page, page.end_cursor))
+            })
+            .and_then(|(has_next_page, end_cursor)| end_cursor.filter(|_| has_next_page))
+    };
+    // Nodes are keyed by their cursor, or their position in pages without cursors, and only cloned when their view is
+    // created, not whenever a page is appended.
+    let edges = move || {
+        let mut edges = Vec::new();
+        let mut push_page = |page_index: usize, page: &CursorPage<T>| {
+            edges.extend((0..page.nodes.len()).map(|index| {
+                let key = page
+                    .cursors
+                    .get(index)
+                    .map_or_else(|| format!("{page_index}:{index}"), ToString::to_string);
+
+                (key, page_index, index)
+            }));
+        };
+
+        first_page.with(|page| {
+            if let Some(Ok(page)) = page {
+                push_page(0, page);
+            }
+        });
+        next_pages.with(|pages| {
+            for (page_index, page) in pages.iter().enumerate() {
+                push_page(page_index + 1, page);
+            }
+        });
+
+        edges
+    };
+    let node = move |page_index: usize, index: usize| match page_index {
+        0 => first_page.with_untracked(|page| match page {
+            Some(Ok(page)) => page.nodes.get(index).cloned(),
+            _ => None,
+        }),
+        _ => next_pages.with_untracked(|pages| pages.get(page_index - 1)?.nodes.get(index).cloned()),
+    };
+
+    let sentinel = NodeRef::<Div>::new();
+    let is_sentinel_visible = RwSignal::new(false);
+
+    Effect::new(move || {
+        use web_sys::wasm_bindgen::JsCast;
+        use web_sys::wasm_bindgen::closure::Closure;
+        use web_sys::{IntersectionObserver, IntersectionObserverEntry, js_sys};
+
+        let Some(element) = sentinel.get() else {
+            return;
+        };
+
+        let callback = Closure::<dyn Fn(js_sys::Array)>::new(move |entries: js_sys::Array| {
+            if let Some(entry) = entries.iter().last() {
+                is_sentinel_visible.set(entry.unchecked_into::<IntersectionObserverEntry>().is_intersecting());
+            }
+        });
+        let Ok(observer) = IntersectionObserver::new(callback.as_ref().unchecked_ref()) else {
+            return;
+        };
+
+        observer.observe(&element);
+
+        let observer = StoredValue::new_local((observer, callback));
+
+        on_cleanup(move || observer.with_value(|(observer, _)| observer.disconnect()));
+    });
+
+    // Keeps loading while the sentinel stays in view, so short pages fill the screen, but stops on errors until retried.
+    Effect::new(move || {
+        if is_sentinel_visible.get()
+            && !load_next_page.pending().get()
+            && !load_next_page.value().with(|value| matches!(value, Some(Err(_))))
+            && let Some(end_cursor) = next_cursor()
+        {
+            load_next_page.dispatch(end_cursor);
+        }
+    });
+
+    let loading = || view! { <span class="loading loading-spinner" aria-label=move || tr!("loading")></span> };
+
+    view! {
+        <div class=format!("infinite-list {class}")>
+            <Transition fallback=loading>
+                {move || {
+                    first_page
+                        .get()
+                        .map(|result| match result {
+                            Err(_) => {
+                                EitherOf3::A(
+                                    view! {
+                                        <div role="alert" class="alert alert-error">
+                                            <span>{move || tr!("loading-failed")}</span>
+                                            <button class="btn btn-sm" on:click=move |_| first_page.refetch()>
+                                                {move || tr!("retry")}
+                                            </button>
+                                        </div>
+                                    },
+                                )
+                            }
+                            Ok(page) if page.nodes.is_empty() => {
+                                EitherOf3::B(
+                                    match &empty {
+                                        Some(empty) => empty.run(),
+                                        None => view! { <p>{move || tr!("no-items")}</p> }.into_any(),
+                                    },
+                                )
+                            }
+                            Ok(_) => {
+                                let children = children.clone();
+
+                                EitherOf3::C(
+                                    view! {
+                                        <For
+                                            each=edges
+                                            key=|(key, _, _)| key.clone()
+                                            children=move |(_, page_index, index)| {
+                                                node(page_index, index).map(children.clone())
+                                            }
+                                        />
+                                    },
+                                )
+                            }
+                        })
+                }}
+            </Transition>
+
+            <Show when=move || load_next_page.pending().get()>{loading}</Show>
+
+            <Show when=move || load_next_page.value().with(|value| matches!(value, Some(Err(_))))>
+                <div role="alert" class="alert alert-error">
+                    <span>{move || tr!("loading-failed")}</span>
+                    <button class="btn btn-sm" on:click=move |_| load_next_page.value().set(None)>
+                        {move || tr!("retry")}
+                    </button>
+                </div>
+            </Show>
+
+            <div node_ref=sentinel aria-hidden="true"></div>
+        </div>
+    }
+}
+
+#[component]
+pub fn LanguageSwitcher(#[prop(optional)] localized_routes: bool) -> impl IntoView {
+    let i18n = use_i18n();
+
+    if localized_routes {
+        let location = use_location();
+
+        Either::Left(view! {
+            <ul class="menu language-switcher">
+                {i18n
+                    .languages
+                    .iter()
+                    .map(|language| {
+                        let is_active = move || i18n.language.get().id == language.id;
+                        let href = move || {
+                            let pathname = location.pathname.get();
+                            localize_path(language.id, split_path(&pathname).1)
+                        };
+
+                        view! {
+                            <li>
+                                <a
+                                    aria-current=move || is_active().then_some("true")
+                                    class:menu-active=is_active
+                                    href=href
+                                    hreflang=language.id.to_string()
+                                    lang=language.id.to_string()
+                                >
+                                    {language.name}
+                                </a>
+                            </li>
+                        }
+                    })
+                    .collect_view()}
+            </ul>
+        })
+    } else {
+        Either::Right(view! {
+            <form action=SetLanguage::url() class="language-switcher" method="post">
+                <ul class="menu">
+                    {i18n
+                        .languages
+                        .iter()
+                        .map(|language| {
+                            let is_active = move || i18n.language.get().id == language.id;
+
+                            view! {
+                                <li>
+                                    <button
+                                        aria-current=move || is_active().then_some("true")
+                                        class:menu-active=is_active
+                                        lang=language.id.to_string()
+                                        name="value"
+                                        type="submit"
+                                        value=language.id.to_string()
+                                        on:click=move |event| {
+                                            event.prevent_default();
+                                            i18n.language.set(language);
+                                        }
+                                    >
+                                        {language.name}
+                                    </button>
+                                </li>
+                            }
+                        })
+                        .collect_view()}
+                </ul>
+            </form>
+        })
+    }
+}
+
 #[component]
 pub fn Modal(
     #[prop(into)] is_open: RwSignal<bool>,
@@ -70,3 +408,163 @@ pub fn Modal(
         </dialog>
     }
 }
+
+#[component]
+pub fn RelativeTime(
+    #[prop(into)] datetime: Signal<OffsetDateTime>,
+    #[prop(default = 60, into)] interval_secs: u64,
+) -> impl IntoView {
+    // Shared with the client so the first render after hydration matches the server.
+    let now = RwSignal::new(SharedValue::new(|| OffsetDateTime::now_utc().unix_timestamp()).into_inner());
+
+    Effect::new(move || {
+        now.set(OffsetDateTime::now_utc().unix_timestamp());
+
+        let handle = set_interval_with_handle(
+            move || now.set(OffsetDateTime::now_utc().unix_timestamp()),
+            std::time::Duration::from_secs(interval_secs),
+        )
+        .ok();
+
+        on_cleanup(move || {
+            if let Some(handle) = handle {
+                handle.clear();
+            }
+        });
+    });
+
+    view! {
+        <time datetime=move || datetime.get().format(&Rfc3339).ok()>
+            {move || relative_time(datetime.get().unix_timestamp() - now.get())}
+        </time>
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TimeUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Months,
+    Years,
+}
+
+// `None` under 10 seconds, in either direction, which is shown as "just now".
+fn time_unit(seconds: i64) -> Option<(TimeUnit, u64)> {
+    let seconds = seconds.unsigned_abs();
+
+    Some(match seconds {
+        0..10 => return None,
+        10..60 => (TimeUnit::Seconds, seconds),
+        60..3_600 => (TimeUnit::Minutes, seconds / 60),
+        3_600..86_400 => (TimeUnit::Hours, seconds / 3_600),
+        86_400..2_592_000 => (TimeUnit::Days, seconds / 86_400),
+        2_592_000..31_536_000 => (TimeUnit::Months, seconds / 2_592_000),
+        _ => (TimeUnit::Years, seconds / 31_536_000),
+    })
+}
+
+fn relative_time(seconds: i64) -> String {
+    let Some((unit, count)) = time_unit(seconds) else {
+        return tr!("just-now");
+    };
+
+    match (unit, seconds > 0) {
+        (TimeUnit::Seconds, false) => tr!("seconds-ago", { "count" => count }),
+        (TimeUnit::Seconds, true) => tr!("in-seconds", { "count" => count }),
+        (TimeUnit::Minutes, false) => tr!("minutes-ago", { "count" => count }),
+        (TimeUnit::Minutes, true) => tr!("in-minutes", { "count" => count }),
+        (TimeUnit::Hours, false) => tr!("hours-ago", { "count" => count }),
+        (TimeUnit::Hours, true) => tr!("in-hours", { "count" => count }),
+        (TimeUnit::Days, false) => tr!("days-ago", { "count" => count }),
+        (TimeUnit::Days, true) => tr!("in-days", { "count" => count }),
+        (TimeUnit::Months, false) => tr!("months-ago", { "count" => count }),
+        (TimeUnit::Months, true) => tr!("in-months", { "count" => count }),
+        (TimeUnit::Years, false) => tr!("years-ago", { "count" => count }),
+        (TimeUnit::Years, true) => tr!("in-years", { "count" => count }),
+    }
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use crate::leptos::{AppProvider, DOT_TRANSLATIONS};
+
+    fn render_relative_time(seconds: i64) -> String {
+        let html = Owner::new().with(|| {
+            view! { <AppProvider translations=&DOT_TRANSLATIONS>{relative_time(seconds)}</AppProvider> }.to_html()
+        });
+
+        // Fluent wraps the arguments in isolation marks.
+        html.split("<div>")
+            .nth(1)
+            .and_then(|html| html.split('<').next())
+            .unwrap()
+            .replace(['\u{2068}', '\u{2069}'], "")
+    }
+
+    #[test]
+    fn picks_the_largest_whole_unit() {
+        for (seconds, expected) in [
+            (0, None),
+            (9, None),
+            (-9, None),
+            (10, Some((TimeUnit::Seconds, 10))),
+            (59, Some((TimeUnit::Seconds, 59))),
+            (60, Some((TimeUnit::Minutes, 1))),
+            (-3_599, Some((TimeUnit::Minutes, 59))),
+            (3_600, Some((TimeUnit::Hours, 1))),
+            (86_400, Some((TimeUnit::Days, 1))),
+            (2_592_000, Some((TimeUnit::Months, 1))),
+            (-31_536_000, Some((TimeUnit::Years, 1))),
+            (i64::MIN, Some((TimeUnit::Years, 292_471_208_677))),
+        ] {
+            assert_eq!(time_unit(seconds), expected, "{seconds}");
+        }
+    }
+
+    #[test]
+    fn formats_past_and_future_times() {
+        for (seconds, expected) in [
+            (0, "just now"),
+            (-9, "just now"),
+            (-10, "10 seconds ago"),
+            (59, "in 59 seconds"),
+            (-60, "1 minute ago"),
+            (60, "in 1 minute"),
+            (-7_200, "2 hours ago"),
+            (7_200, "in 2 hours"),
+        ] {
+            assert_eq!(render_relative_time(seconds), expected, "{seconds}");
+        }
+    }
+
+    #[tokio::test]
+    async fn renders_pages_without_cursors() {
+        use futures::StreamExt;
+
+        let _ = any_spawner::Executor::init_tokio();
+        let html = Owner::new()
+            .with(|| {
+                view! {
+                    <AppProvider translations=&DOT_TRANSLATIONS>
+                        <InfiniteList
+                            fetch_page=|_| async {
+                                Ok(CursorPage {
+                                    nodes: vec!["a".to_owned(), "b".to_owned()],
+                                    ..Default::default()
+                                })
+                            }
+                            children=|node: String| view! { <p>{node}</p> }
+                        />
+                    </AppProvider>
+                }
+                .to_html_stream_in_order()
+            })
+            .collect::<String>()
+            .await;
+
+        assert!(html.contains("<p>a</p><p>b</p>"), "{html}");
+    }
+}
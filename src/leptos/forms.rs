@@ -114,7 +114,7 @@ where
                                                 }
                                             }
                                         >
-                                            "Ok"
+                                            {move_tr!("ok")}
                                         </button>
                                     </div>
                                 </Modal>
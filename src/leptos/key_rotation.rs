@@ -0,0 +1,193 @@
+use std::sync::Arc;
+
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::http::header::COOKIE;
+use axum::middleware::Next;
+use axum::response::Response;
+use cookie::{Cookie, CookieJar, Key};
+use tower_sessions::Session;
+
+#[derive(Clone)]
+struct RotatedSessionKey;
+
+pub(crate) struct SessionKeys {
+    pub current: Key,
+    pub name: String,
+    pub previous: Vec<Key>,
+}
+
+impl SessionKeys {
+    fn reencrypt(&self, cookie: &Cookie<'_>) -> Option<String> {
+        if cookie.name() != self.name {
+            return None;
+        }
+
+        let cookie = Cookie::new(self.name.clone(), cookie.value().to_owned());
+        let jar = CookieJar::new();
+
+        if jar.private(&self.current).decrypt(cookie.clone()).is_some() {
+            return None;
+        }
+
+        let decrypted = self
+            .previous
+            .iter()
+            .find_map(|key| jar.private(key).decrypt(cookie.clone()))?;
+
+        let mut jar = CookieJar::new();
+
+        jar.private_mut(&self.current).add(decrypted);
+
+        jar.get(&self.name).map(|cookie| cookie.value().to_owned())
+    }
+}
+
+// Re-encrypts a session cookie encrypted with a previous key so the session layer accepts it.
+pub(crate) async fn rotate_session_key(keys: Arc<SessionKeys>, mut request: Request, next: Next) -> Response {
+    let header = request
+        .headers()
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let mut is_rotated = false;
+
+    let cookies = Cookie::split_parse(header)
+        .filter_map(Result::ok)
+        .map(|cookie| match keys.reencrypt(&cookie) {
+            Some(value) => {
+                is_rotated = true;
+                format!("{}={value}", cookie.name())
+            }
+            None => cookie.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    if is_rotated && let Ok(value) = HeaderValue::from_str(&cookies) {
+        request.headers_mut().insert(COOKIE, value);
+        request.extensions_mut().insert(RotatedSessionKey);
+    }
+
+    next.run(request).await
+}
+
+// Marks a rotated session as modified so its cookie is issued again with the current key.
+pub(crate) async fn reissue_session_cookie(request: Request, next: Next) -> Response {
+    if request.extensions().get::<RotatedSessionKey>().is_some()
+        && let Some(session) = request.extensions().get::<Session>()
+    {
+        session.set_expiry(session.expiry());
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Router;
+    use axum::body::{Body, to_bytes};
+    use axum::middleware::from_fn;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn session_keys() -> SessionKeys {
+        SessionKeys {
+            current: Key::from(&[1; 64]),
+            name: "_session".to_owned(),
+            previous: vec![Key::from(&[2; 64])],
+        }
+    }
+
+    fn encrypt(key: &Key, value: &str) -> String {
+        let mut jar = CookieJar::new();
+
+        jar.private_mut(key).add(Cookie::new("_session", value.to_owned()));
+
+        jar.get("_session").unwrap().value().to_owned()
+    }
+
+    fn decrypt(key: &Key, value: &str) -> Option<String> {
+        CookieJar::new()
+            .private(key)
+            .decrypt(Cookie::new("_session", value.to_owned()))
+            .map(|cookie| cookie.value().to_owned())
+    }
+
+    #[test]
+    fn reencrypts_cookies_from_previous_keys_with_the_current_key() {
+        let session_keys = session_keys();
+        let cookie = Cookie::new("_session", encrypt(&session_keys.previous[0], "session-id"));
+
+        let value = session_keys.reencrypt(&cookie).unwrap();
+
+        assert_eq!(decrypt(&session_keys.current, &value).as_deref(), Some("session-id"));
+        assert_eq!(decrypt(&session_keys.previous[0], &value), None);
+    }
+
+    #[test]
+    fn leaves_current_unknown_and_other_cookies_untouched() {
+        let session_keys = session_keys();
+
+        for cookie in [
+            Cookie::new("_session", encrypt(&session_keys.current, "session-id")),
+            Cookie::new("_session", encrypt(&Key::from(&[3; 64]), "session-id")),
+            Cookie::new("theme", encrypt(&session_keys.previous[0], "dark")),
+        ] {
+            assert_eq!(session_keys.reencrypt(&cookie), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn rewrites_the_cookie_header_only_for_rotated_sessions() {
+        let session_keys = Arc::new(session_keys());
+        let router = Router::new()
+            .route(
+                "/",
+                get(|request: Request| async move {
+                    format!(
+                        "{}|{}",
+                        request.headers()[COOKIE].to_str().unwrap(),
+                        request.extensions().get::<RotatedSessionKey>().is_some()
+                    )
+                }),
+            )
+            .layer(from_fn({
+                let session_keys = session_keys.clone();
+
+                move |request, next| rotate_session_key(session_keys.clone(), request, next)
+            }));
+
+        let current = format!("theme=dark; _session={}", encrypt(&session_keys.current, "session-id"));
+        let previous = format!(
+            "theme=dark; _session={}",
+            encrypt(&session_keys.previous[0], "session-id")
+        );
+
+        for (header, is_rotated) in [(current.clone(), false), (previous, true)] {
+            let request = axum::http::Request::get("/")
+                .header(COOKIE, header)
+                .body(Body::empty())
+                .unwrap();
+            let body = to_bytes(router.clone().oneshot(request).await.unwrap().into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let (cookies, rotated) = std::str::from_utf8(&body).unwrap().split_once('|').unwrap();
+
+            assert_eq!(rotated, is_rotated.to_string());
+            assert!(cookies.starts_with("theme=dark; _session="));
+
+            let value = cookies.trim_start_matches("theme=dark; _session=");
+
+            assert_eq!(decrypt(&session_keys.current, value).as_deref(), Some("session-id"));
+
+            if !is_rotated {
+                assert_eq!(cookies, current);
+            }
+        }
+    }
+}
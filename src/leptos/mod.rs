@@ -4,6 +4,7 @@ use fluent_templates::{StaticLoader, static_loader};
 use leptos::prelude::*;
 use leptos_fluent::leptos_fluent;
 use leptos_meta::provide_meta_context;
+use time_tz::{TimeZone, Tz, timezones};
 
 pub use leptos_fluent::{I18n, move_tr, tr};
 
@@ -13,13 +14,21 @@ pub use leptos_axum::{extract, redirect};
 pub mod components;
 pub mod forms;
 pub mod icons;
+pub mod routing;
 
+#[cfg(feature = "server")]
+mod key_rotation;
+#[cfg(feature = "server")]
+mod server;
 mod server_functions;
 
-use server_functions::{get_language, set_language};
+#[cfg(feature = "server")]
+pub use server::ServerBuilder;
+
+use server_functions::set_language;
 
 #[cfg(feature = "server")]
-use crate::constants::SESSION_KEY_LANGUAGE;
+use crate::constants::{SESSION_KEY_LANGUAGE, SESSION_KEY_TIMEZONE};
 
 static_loader! {
     static DOT_TRANSLATIONS = {
@@ -28,18 +37,134 @@ static_loader! {
     };
 }
 
+#[cfg(feature = "server")]
+static LANGUAGES: LazyLock<Vec<unic_langid::LanguageIdentifier>> = LazyLock::new(|| {
+    serde_yaml::from_str::<Vec<Vec<String>>>(include_str!("../../locales/languages.yaml"))
+        .expect("Could not parse languages.yaml")
+        .into_iter()
+        .filter_map(|language| language.first()?.parse().ok())
+        .collect()
+});
+
 pub fn use_i18n() -> I18n {
     use_context().unwrap()
 }
 
+#[derive(Clone, Copy)]
+struct TimezoneContext(RwSignal<String>);
+
+pub fn use_timezone() -> Signal<&'static Tz> {
+    let TimezoneContext(timezone) = use_context().expect("Could not get timezone");
+
+    Signal::derive(move || timezones::get_by_name(&timezone.get()).unwrap_or(timezones::db::UTC))
+}
+
+#[cfg(feature = "server")]
+#[derive(Clone)]
+struct RequestLanguage(unic_langid::LanguageIdentifier);
+
+#[cfg(feature = "server")]
+#[derive(Clone)]
+struct RequestTimezone(&'static Tz);
+
 #[cfg(feature = "server")]
 pub async fn extract_language() -> Result<unic_langid::LanguageIdentifier, ServerFnError> {
     let session = extract_session().await?;
+    let headers = extract::<axum::http::HeaderMap>().await?;
+
+    Ok(resolve_language(&session, &headers).await?)
+}
 
+#[cfg(feature = "server")]
+async fn resolve_language(
+    session: &tower_sessions::Session,
+    headers: &axum::http::HeaderMap,
+) -> Result<unic_langid::LanguageIdentifier, tower_sessions::session::Error> {
+    use axum::http::header::ACCEPT_LANGUAGE;
+
+    // The session may still hold a language that is no longer configured.
+    if let Some(language) = session
+        .get::<unic_langid::LanguageIdentifier>(SESSION_KEY_LANGUAGE)
+        .await?
+        .and_then(|language| match_language(&[language]))
+    {
+        return Ok(language);
+    }
+
+    Ok(negotiate_language(
+        headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default(),
+    ))
+}
+
+#[cfg(feature = "server")]
+pub async fn extract_timezone() -> Result<&'static Tz, ServerFnError> {
+    let session = extract_session().await?;
+
+    Ok(resolve_timezone(&session).await?)
+}
+
+#[cfg(feature = "server")]
+async fn resolve_timezone(session: &tower_sessions::Session) -> Result<&'static Tz, tower_sessions::session::Error> {
     Ok(session
-        .get(SESSION_KEY_LANGUAGE)
+        .get::<String>(SESSION_KEY_TIMEZONE)
         .await?
-        .unwrap_or_else(|| unic_langid::langid!("en")))
+        .and_then(|name| timezones::get_by_name(&name))
+        .unwrap_or(timezones::db::UTC))
+}
+
+// Resolves the language and timezone before rendering, so they can be read synchronously.
+#[cfg(feature = "server")]
+pub(crate) async fn provide_request_preferences(
+    mut request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    if let Some(session) = request.extensions().get::<tower_sessions::Session>().cloned() {
+        if let Ok(language) = resolve_language(&session, request.headers()).await {
+            request.extensions_mut().insert(RequestLanguage(language));
+        }
+
+        if let Ok(timezone) = resolve_timezone(&session).await {
+            request.extensions_mut().insert(RequestTimezone(timezone));
+        }
+    }
+
+    next.run(request).await
+}
+
+#[cfg(feature = "server")]
+pub fn negotiate_language(accept_language: &str) -> unic_langid::LanguageIdentifier {
+    // `fluent_langneg::accepted_languages::parse` keeps the header order and ignores the `q` weights.
+    let mut weighted = accept_language
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';').map(str::trim);
+            let language = parts.next()?.parse::<unic_langid::LanguageIdentifier>().ok()?;
+            let weight = match parts.find_map(|part| part.strip_prefix("q=")) {
+                Some(weight) => weight.parse::<f32>().ok()?,
+                None => 1.0,
+            };
+
+            (weight > 0.0).then_some((language, weight))
+        })
+        .collect::<Vec<_>>();
+
+    weighted.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    let requested = weighted.into_iter().map(|(language, _)| language).collect::<Vec<_>>();
+
+    match_language(&requested).unwrap_or_else(|| DOT_TRANSLATIONS.fallback().clone())
+}
+
+#[cfg(feature = "server")]
+fn match_language(requested: &[unic_langid::LanguageIdentifier]) -> Option<unic_langid::LanguageIdentifier> {
+    use fluent_langneg::{NegotiationStrategy, negotiate_languages};
+
+    negotiate_languages(requested, &LANGUAGES, None, NegotiationStrategy::Lookup)
+        .first()
+        .map(|language| (*language).clone())
 }
 
 #[cfg(feature = "server")]
@@ -62,7 +187,7 @@ pub async fn serve<IV>(app_fn: fn() -> IV) -> anyhow::Result<()>
 where
     IV: IntoView + 'static,
 {
-    serve_with_axum_router(app_fn, axum::Router::new()).await
+    ServerBuilder::new(app_fn).serve().await
 }
 
 #[cfg(feature = "server")]
@@ -73,53 +198,7 @@ pub async fn serve_with_axum_router<IV>(
 where
     IV: IntoView + 'static,
 {
-    use cookie::{Key, SameSite};
-    use fred::prelude::{ClientLike, Config, Pool};
-    use leptos::config::get_configuration;
-    use leptos_axum::{LeptosRoutes, file_and_error_handler, generate_route_list};
-    use time::Duration;
-    use tokio::net::TcpListener;
-    use tower_sessions::{Expiry, SessionManagerLayer};
-    use tower_sessions_redis_store::RedisStore;
-
-    use crate::config::SESSION_CONFIG;
-
-    let leptos_options = get_configuration(None)?.leptos_options;
-    let addr = leptos_options.site_addr;
-    let routes = generate_route_list(app_fn);
-    let redis_pool = Pool::new(Config::from_url(&SESSION_CONFIG.redis_url)?, None, None, None, 10)?;
-
-    let redis_conn = redis_pool.connect();
-    redis_pool.wait_for_connect().await?;
-
-    let session_store = RedisStore::new(redis_pool);
-    let session_layer = SessionManagerLayer::new(session_store)
-        .with_domain(SESSION_CONFIG.domain.clone())
-        .with_expiry(Expiry::OnInactivity(Duration::days(30)))
-        .with_http_only(true)
-        .with_name(SESSION_CONFIG.name.clone())
-        .with_private(Key::from(SESSION_CONFIG.key.as_bytes()))
-        .with_same_site(SameSite::Strict)
-        .with_secure(SESSION_CONFIG.secure);
-
-    let shell = move |options| shell_with_app(options, app_fn);
-
-    let app = router
-        .leptos_routes(&leptos_options.clone(), routes, {
-            let leptos_options = leptos_options.clone();
-            move || shell_with_app(leptos_options.clone(), app_fn)
-        })
-        .fallback(file_and_error_handler(shell))
-        .layer(session_layer)
-        .with_state(leptos_options);
-
-    let listener = TcpListener::bind(&addr).await?;
-
-    axum::serve(listener, app.into_make_service()).await?;
-
-    redis_conn.await.unwrap()?;
-
-    Ok(())
+    ServerBuilder::new(app_fn).router(router).serve().await
 }
 
 #[cfg(feature = "server")]
@@ -127,12 +206,23 @@ fn shell_with_app<IV>(options: leptos::config::LeptosOptions, app_fn: fn() -> IV
 where
     IV: IntoView + 'static,
 {
+    use axum::http::request::Parts;
     use leptos::hydration::{AutoReload, HydrationScripts};
     use leptos_meta::{HashedStylesheet, MetaTags};
+    use unic_langid::CharacterDirection;
+
+    let language = use_context::<Parts>()
+        .and_then(|parts| parts.extensions.get::<RequestLanguage>().cloned())
+        .map(|request_language| request_language.0)
+        .unwrap_or_else(|| DOT_TRANSLATIONS.fallback().clone());
+    let dir = match language.character_direction() {
+        CharacterDirection::RTL => "rtl",
+        _ => "ltr",
+    };
 
     view! {
         <!DOCTYPE html>
-        <html lang="en">
+        <html lang=language.to_string() dir=dir>
             <head>
                 <meta charset="utf-8" />
                 <meta
@@ -156,6 +246,7 @@ pub fn AppProvider(
     children: Children,
 ) -> impl IntoView {
     provide_meta_context();
+    provide_timezone();
 
     let is_done = RwSignal::new(false);
 
@@ -175,21 +266,158 @@ pub fn AppProvider(
     }
 }
 
+// The timezone rendered by the server is shared with the client, which then replaces it with the browser timezone.
+fn provide_timezone() {
+    let timezone = RwSignal::new(
+        SharedValue::new(|| {
+            #[cfg(feature = "server")]
+            if let Some(RequestTimezone(timezone)) = use_context::<axum::http::request::Parts>()
+                .and_then(|parts| parts.extensions.get::<RequestTimezone>().cloned())
+            {
+                return timezone.name().to_owned();
+            }
+
+            timezones::db::UTC.name().to_owned()
+        })
+        .into_inner(),
+    );
+
+    provide_context(TimezoneContext(timezone));
+
+    #[cfg(feature = "hydrate")]
+    Effect::new(move || {
+        if let Ok(system_timezone) = time_tz::system::get_timezone()
+            && system_timezone.name() != timezone.get_untracked()
+        {
+            timezone.set(system_timezone.name().to_owned());
+
+            leptos::task::spawn_local(async move {
+                let _ = server_functions::set_timezone(system_timezone.name().to_owned()).await;
+            });
+        }
+    });
+}
+
 #[component]
 fn I18nProvider(translations: &'static LazyLock<StaticLoader>, children: Children) -> impl IntoView {
     leptos_fluent! {
         #[cfg(debug_assertions)]
         check_translations: "./src/**/*.rs",
 
+        default_language: "en",
         languages: "./locales/languages.yaml",
         locales: "./locales",
-        sync_html_tag_dir: true,
-        sync_html_tag_lang: true,
         translations: [DOT_TRANSLATIONS, translations],
 
-        initial_language_from_server_function: get_language,
         set_language_to_server_function: set_language,
 
-        children: children(),
+        children: {
+            sync_html_tag_language();
+            children()
+        },
+    }
+}
+
+// The server and the client must start from the same language to hydrate, so it is taken from the request on
+// the server and from the `lang` attribute rendered by the shell on the client.
+fn sync_html_tag_language() {
+    let i18n = use_i18n();
+
+    #[cfg(feature = "server")]
+    let initial_language = use_context::<axum::http::request::Parts>().and_then(|parts| {
+        parts
+            .extensions
+            .get::<RequestLanguage>()
+            .map(|language| language.0.to_string())
+    });
+
+    #[cfg(all(feature = "hydrate", not(feature = "server")))]
+    let initial_language = document()
+        .document_element()
+        .and_then(|html| html.get_attribute("lang"));
+
+    #[cfg(not(any(feature = "hydrate", feature = "server")))]
+    let initial_language = None::<String>;
+
+    if let Some(language) =
+        initial_language.and_then(|id| i18n.languages.iter().find(|language| language.id.to_string() == id))
+    {
+        i18n.language.set(language);
+    }
+
+    Effect::new(move || {
+        let language = i18n.language.get();
+
+        if let Some(html) = document().document_element() {
+            let _ = html.set_attribute("lang", &language.id.to_string());
+            let _ = html.set_attribute("dir", language.dir.as_str());
+        }
+    });
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiates_the_language_by_weight() {
+        for (accept_language, expected) in [
+            ("de-CH;q=0.5, fr;q=0.8", "fr"),
+            ("fr;q=0.8, de-CH", "de"),
+            ("pt-BR,pt;q=0.9,en;q=0.8", "pt"),
+            ("es;q=0, ar;q=0.1", "ar"),
+            ("*, xx-YY;q=0.9", "en"),
+            ("en-US;q=abc, de;q=0.2", "de"),
+            ("", "en"),
+        ] {
+            assert_eq!(
+                negotiate_language(accept_language).to_string(),
+                expected,
+                "{accept_language}"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_only_configured_languages() {
+        let language = |id: &str| id.parse::<unic_langid::LanguageIdentifier>().unwrap();
+
+        assert_eq!(match_language(&[language("de-CH")]), Some(language("de")));
+        assert_eq!(
+            match_language(&[language("ja"), language("pt-BR")]),
+            Some(language("pt"))
+        );
+        assert_eq!(match_language(&[language("ja")]), None);
+    }
+
+    #[cfg(feature = "session-memory")]
+    #[tokio::test]
+    async fn ignores_session_languages_that_are_not_configured() {
+        use std::sync::Arc;
+
+        use axum::http::HeaderMap;
+        use axum::http::header::ACCEPT_LANGUAGE;
+        use tower_sessions::{MemoryStore, Session};
+
+        let session = Session::new(None, Arc::new(MemoryStore::default()), None);
+        let mut headers = HeaderMap::new();
+
+        headers.insert(ACCEPT_LANGUAGE, "fr".parse().unwrap());
+
+        for (session_language, expected) in [("es", "es"), ("de-AT", "de"), ("ja", "fr")] {
+            session
+                .insert(
+                    SESSION_KEY_LANGUAGE,
+                    session_language.parse::<unic_langid::LanguageIdentifier>().unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(
+                resolve_language(&session, &headers).await.unwrap().to_string(),
+                expected,
+                "{session_language}"
+            );
+        }
     }
 }
@@ -0,0 +1,133 @@
+use fluent_templates::LanguageIdentifier;
+use leptos::prelude::*;
+use leptos_meta::Link;
+use leptos_router::components::Outlet;
+use leptos_router::hooks::use_location;
+
+use super::{DOT_TRANSLATIONS, use_i18n};
+
+pub fn localize_path(language: &LanguageIdentifier, path: &str) -> String {
+    match path.trim_start_matches('/') {
+        "" => format!("/{language}"),
+        path => format!("/{language}/{path}"),
+    }
+}
+
+pub fn localized_path(path: &str) -> String {
+    localize_path(use_i18n().language.get().id, path)
+}
+
+pub(super) fn split_path(path: &str) -> (&str, &str) {
+    let path = path.trim_start_matches('/');
+
+    path.split_once('/').unwrap_or((path, ""))
+}
+
+#[component]
+pub fn LocalizedOutlet() -> impl IntoView {
+    let i18n = use_i18n();
+    let location = use_location();
+    let origin = request_origin();
+
+    Effect::new(move || {
+        let pathname = location.pathname.get();
+        let (segment, _) = split_path(&pathname);
+
+        if let Some(language) = i18n.languages.iter().find(|language| *language.id == segment)
+            && language.id != i18n.language.get_untracked().id
+        {
+            i18n.language.set(language);
+        }
+    });
+
+    view! {
+        {move || {
+            let pathname = location.pathname.get();
+            let (_, path) = split_path(&pathname);
+            let default_href = match path {
+                "" => format!("{origin}/"),
+                path => format!("{origin}{}", localize_path(DOT_TRANSLATIONS.fallback(), path)),
+            };
+            let alternates = i18n.languages
+                .iter()
+                .map(|language| {
+                    let href = format!("{origin}{}", localize_path(language.id, path));
+
+                    view! { <Link rel="alternate" hreflang=language.id.to_string() href=href /> }
+                })
+                .collect_view();
+
+            view! {
+                {alternates}
+                <Link rel="alternate" hreflang="x-default" href=default_href />
+            }
+        }}
+        <Outlet />
+    }
+}
+
+// Alternate links must be absolute, so the origin of the request is shared with the client to hydrate.
+fn request_origin() -> String {
+    SharedValue::new(|| {
+        #[cfg(feature = "server")]
+        if let Some(parts) = use_context::<axum::http::request::Parts>() {
+            use axum::http::header::HOST;
+
+            let scheme = parts
+                .headers
+                .get("x-forwarded-proto")
+                .and_then(|value| value.to_str().ok())
+                .or(parts.uri.scheme_str())
+                .unwrap_or("http");
+            let host = parts
+                .headers
+                .get(HOST)
+                .and_then(|value| value.to_str().ok())
+                .or(parts.uri.authority().map(|authority| authority.as_str()));
+
+            if let Some(host) = host {
+                return format!("{scheme}://{host}");
+            }
+        }
+
+        String::new()
+    })
+    .into_inner()
+}
+
+// Takes the language from the first path segment, or redirects `/` to the negotiated language.
+#[cfg(feature = "server")]
+pub(crate) async fn localize_request(
+    mut request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::response::{IntoResponse, Redirect};
+
+    use super::{DOT_TRANSLATIONS, LANGUAGES, RequestLanguage};
+
+    let (segment, _) = split_path(request.uri().path());
+
+    if let Some(language) = LANGUAGES.iter().find(|language| **language == segment) {
+        let language = language.clone();
+
+        request.extensions_mut().insert(RequestLanguage(language));
+
+        return next.run(request).await;
+    }
+
+    if request.uri().path() != "/" {
+        return next.run(request).await;
+    }
+
+    let language = request
+        .extensions()
+        .get::<RequestLanguage>()
+        .map(|request_language| request_language.0.clone())
+        .unwrap_or_else(|| DOT_TRANSLATIONS.fallback().clone());
+    let location = match request.uri().query() {
+        Some(query) => format!("{}?{query}", localize_path(&language, "/")),
+        None => localize_path(&language, "/"),
+    };
+
+    Redirect::temporary(&location).into_response()
+}
@@ -0,0 +1,273 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::{FromRef, Request};
+use axum::response::IntoResponse;
+use axum::routing::Route;
+use cookie::SameSite;
+use leptos::config::LeptosOptions;
+use leptos::prelude::IntoView;
+use time::Duration;
+use tokio::net::TcpListener;
+use tower::{Layer, Service};
+use tower_sessions::Expiry;
+
+use super::key_rotation::{SessionKeys, reissue_session_cookie, rotate_session_key};
+use super::routing::localize_request;
+use super::{provide_request_preferences, shell_with_app};
+
+type RouterFn<S> = Box<dyn FnOnce(Router<S>) -> Router<S> + Send>;
+type ShutdownSignal = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+pub struct ServerBuilder<IV, S = LeptosOptions> {
+    app_fn: fn() -> IV,
+    addr: Option<SocketAddr>,
+    expiry: Expiry,
+    layers: Vec<RouterFn<S>>,
+    listener: Option<TcpListener>,
+    localized_routes: bool,
+    pool_size: usize,
+    router: Router<S>,
+    same_site: SameSite,
+    shutdown_signal: Option<ShutdownSignal>,
+    shutdown_timeout: std::time::Duration,
+    state_fn: Box<dyn FnOnce(LeptosOptions) -> S + Send>,
+}
+
+impl<IV> ServerBuilder<IV>
+where
+    IV: IntoView + 'static,
+{
+    pub fn new(app_fn: fn() -> IV) -> Self {
+        Self::new_with_state(app_fn, |leptos_options| leptos_options)
+    }
+}
+
+impl<IV, S> ServerBuilder<IV, S>
+where
+    IV: IntoView + 'static,
+    S: Clone + Send + Sync + 'static,
+    LeptosOptions: FromRef<S>,
+{
+    pub fn new_with_state<F>(app_fn: fn() -> IV, state_fn: F) -> Self
+    where
+        F: FnOnce(LeptosOptions) -> S + Send + 'static,
+    {
+        Self {
+            app_fn,
+            addr: None,
+            expiry: Expiry::OnInactivity(Duration::days(30)),
+            layers: Vec::new(),
+            listener: None,
+            localized_routes: false,
+            pool_size: 10,
+            router: Router::new(),
+            same_site: SameSite::Strict,
+            shutdown_signal: None,
+            shutdown_timeout: std::time::Duration::from_secs(30),
+            state_fn: Box::new(state_fn),
+        }
+    }
+
+    pub fn addr(mut self, addr: SocketAddr) -> Self {
+        self.addr = Some(addr);
+        self
+    }
+
+    pub fn expiry(mut self, expiry: Expiry) -> Self {
+        self.expiry = expiry;
+        self
+    }
+
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + Sync + 'static,
+        L::Service: Service<Request> + Clone + Send + Sync + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        self.layers.push(Box::new(move |router| router.layer(layer)));
+        self
+    }
+
+    pub fn listener(mut self, listener: TcpListener) -> Self {
+        self.listener = Some(listener);
+        self
+    }
+
+    pub fn localized_routes(mut self, localized_routes: bool) -> Self {
+        self.localized_routes = localized_routes;
+        self
+    }
+
+    pub fn pool_size(mut self, pool_size: usize) -> Self {
+        self.pool_size = pool_size;
+        self
+    }
+
+    pub fn router(mut self, router: Router<S>) -> Self {
+        self.router = router;
+        self
+    }
+
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = same_site;
+        self
+    }
+
+    pub fn shutdown_signal<F>(mut self, signal: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.shutdown_signal = Some(Box::pin(signal));
+        self
+    }
+
+    pub fn shutdown_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    pub async fn serve(self) -> anyhow::Result<()> {
+        use axum::middleware::from_fn;
+        use cookie::Key;
+        use leptos::config::get_configuration;
+        use leptos_axum::{LeptosRoutes, file_and_error_handler, generate_route_list};
+        use tower_sessions::SessionManagerLayer;
+
+        use crate::config::{SESSION_CONFIG, validate_config};
+        use crate::session_store::DotSessionStore;
+
+        validate_config()?;
+
+        let app_fn = self.app_fn;
+        let leptos_options = get_configuration(None)?.leptos_options;
+        let routes = generate_route_list(app_fn);
+
+        let session_store = DotSessionStore::connect(&SESSION_CONFIG, self.pool_size).await?;
+        let session_layer = SessionManagerLayer::new(session_store.clone())
+            .with_domain(SESSION_CONFIG.domain.clone())
+            .with_expiry(self.expiry)
+            .with_http_only(true)
+            .with_name(SESSION_CONFIG.name.clone())
+            .with_private(Key::from(SESSION_CONFIG.key.as_bytes()))
+            .with_same_site(self.same_site)
+            .with_secure(SESSION_CONFIG.secure);
+
+        let shell = move |options| shell_with_app(options, app_fn);
+        let state = (self.state_fn)(leptos_options.clone());
+
+        let mut router = self
+            .router
+            .leptos_routes(&state, routes, {
+                let leptos_options = leptos_options.clone();
+                move || shell_with_app(leptos_options.clone(), app_fn)
+            })
+            .fallback(file_and_error_handler(shell));
+
+        for layer_fn in self.layers {
+            router = layer_fn(router);
+        }
+
+        if self.localized_routes {
+            router = router.layer(from_fn(localize_request));
+        }
+
+        router = router.layer(from_fn(provide_request_preferences));
+
+        if SESSION_CONFIG.previous_keys.is_empty() {
+            router = router.layer(session_layer);
+        } else {
+            let session_keys = Arc::new(SessionKeys {
+                current: Key::from(SESSION_CONFIG.key.as_bytes()),
+                name: SESSION_CONFIG.name.clone(),
+                previous: SESSION_CONFIG
+                    .previous_keys
+                    .iter()
+                    .map(|key| Key::from(key.as_bytes()))
+                    .collect(),
+            });
+
+            router = router
+                .layer(from_fn(reissue_session_cookie))
+                .layer(session_layer)
+                .layer(from_fn(move |request, next| {
+                    rotate_session_key(session_keys.clone(), request, next)
+                }));
+        }
+
+        let listener = match self.listener {
+            Some(listener) => listener,
+            None => TcpListener::bind(self.addr.unwrap_or(leptos_options.site_addr)).await?,
+        };
+
+        let app = router.with_state(state);
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+        let shutdown_signal = self.shutdown_signal;
+
+        let server = axum::serve(listener, app.into_make_service()).with_graceful_shutdown(async move {
+            wait_for_shutdown(shutdown_signal).await;
+
+            log::info!("Shutting down, waiting for connections to close");
+
+            let _ = shutdown_tx.send(());
+        });
+
+        let drain_timeout = async {
+            if shutdown_rx.await.is_ok() {
+                tokio::time::sleep(self.shutdown_timeout).await;
+            } else {
+                std::future::pending::<()>().await;
+            }
+        };
+
+        // Axum runs every connection in its own task, so the store is closed once they are done, or after the timeout
+        // since the remaining ones are dropped with the runtime anyway.
+        tokio::select! {
+            result = server => result?,
+            _ = drain_timeout => {
+                log::warn!("Shutdown timeout reached, exiting without waiting for the remaining connections");
+            }
+        }
+
+        session_store.close().await?;
+
+        Ok(())
+    }
+}
+
+async fn wait_for_shutdown(shutdown_signal: Option<ShutdownSignal>) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("Could not install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Could not install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    let custom = async {
+        match shutdown_signal {
+            Some(signal) => signal.await,
+            None => std::future::pending().await,
+        }
+    };
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+        _ = custom => {},
+    }
+}
@@ -1,18 +1,26 @@
 use leptos::prelude::{ServerFnError, server};
 
 #[cfg(feature = "server")]
-use super::{SESSION_KEY_LANGUAGE, extract_language, extract_session};
+use super::{SESSION_KEY_LANGUAGE, SESSION_KEY_TIMEZONE, extract_session, match_language};
 
 #[server]
-pub async fn get_language() -> Result<Option<String>, ServerFnError> {
-    Ok(Some(extract_language().await?.to_string()))
+pub async fn set_language(value: String) -> Result<(), ServerFnError> {
+    let Some(language) = match_language(&[value.parse::<unic_langid::LanguageIdentifier>()?]) else {
+        return Err(ServerFnError::new(format!("Unknown language: {value}")));
+    };
+
+    let session = extract_session().await?;
+
+    Ok(session.insert(SESSION_KEY_LANGUAGE, language).await?)
 }
 
 #[server]
-pub async fn set_language(value: String) -> Result<(), ServerFnError> {
+pub async fn set_timezone(value: String) -> Result<(), ServerFnError> {
+    if time_tz::timezones::get_by_name(&value).is_none() {
+        return Err(ServerFnError::new(format!("Unknown timezone: {value}")));
+    }
+
     let session = extract_session().await?;
 
-    Ok(session
-        .insert(SESSION_KEY_LANGUAGE, value.parse::<unic_langid::LanguageIdentifier>()?)
-        .await?)
+    Ok(session.insert(SESSION_KEY_TIMEZONE, value).await?)
 }
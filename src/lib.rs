@@ -4,6 +4,7 @@ pub mod config;
 #[cfg(feature = "server")]
 mod constants {
     pub const SESSION_KEY_LANGUAGE: &str = "language";
+    pub const SESSION_KEY_TIMEZONE: &str = "timezone";
 }
 
 #[cfg(feature = "server")]
@@ -14,8 +15,16 @@ pub mod axum {
     pub use axum::response::{IntoResponse, Redirect};
     pub use axum::routing::{get, post};
     pub use axum::{Json, Router};
-    pub use tower_sessions::Session;
+    pub use cookie::SameSite;
+    pub use tower_sessions::{Expiry, Session};
 }
 
+pub mod format;
+pub mod i18n;
+#[cfg(any(feature = "cli", feature = "server"))]
+pub mod keys;
 pub mod leptos;
 pub mod pagination;
+
+#[cfg(feature = "server")]
+pub mod session_store;
@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "dot", about = "Utilities for Mango³ applications")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a random key to use as SESSION_KEY
+    GenerateKey,
+    /// Translation utilities
+    I18n {
+        #[command(subcommand)]
+        command: I18nCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum I18nCommand {
+    /// Report missing, unused and unbalanced-argument translation keys
+    Check {
+        #[arg(long, default_value = "src")]
+        sources: PathBuf,
+        #[arg(long, default_value = "locales")]
+        locales: PathBuf,
+    },
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::GenerateKey => println!("{}", dot::keys::generate_session_key()),
+        Command::I18n {
+            command: I18nCommand::Check { sources, locales },
+        } => match dot::i18n::check(&sources, &locales) {
+            Ok(problems) if problems.is_empty() => println!("All translations are complete"),
+            Ok(problems) => {
+                for problem in &problems {
+                    eprintln!("{problem}");
+                }
+
+                eprintln!("{} translation problem(s) found", problems.len());
+
+                return ExitCode::FAILURE;
+            }
+            Err(error) => {
+                eprintln!("Could not check translations: {error}");
+
+                return ExitCode::FAILURE;
+            }
+        },
+    }
+
+    ExitCode::SUCCESS
+}
@@ -1,81 +1,1140 @@
-#[cfg(feature = "server")]
+use std::fmt;
 use std::future::Future;
+#[cfg(feature = "server")]
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::str::FromStr;
+#[cfg(feature = "server")]
+use std::sync::LazyLock;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use validator::{Validate, ValidationError, ValidationErrors};
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Cursor(String);
+
+impl Cursor {
+    pub fn encode<K: Serialize>(keys: &K) -> Self {
+        Self(URL_SAFE_NO_PAD.encode(serde_json::to_vec(keys).expect("Could not serialize cursor keys")))
+    }
+
+    pub fn decode<K: DeserializeOwned>(&self) -> Result<K, CursorError> {
+        let keys = URL_SAFE_NO_PAD.decode(&self.0).map_err(|_| CursorError::Invalid)?;
+
+        serde_json::from_slice(&keys).map_err(|_| CursorError::Invalid)
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SignedCursor(String);
+
+#[cfg(feature = "server")]
+impl SignedCursor {
+    pub fn sign<K: Serialize>(keys: &K) -> Self {
+        use crate::config::PAGINATION_CONFIG;
+
+        let cursor_ttl = i64::try_from(PAGINATION_CONFIG.cursor_ttl).unwrap_or(i64::MAX);
+
+        Self::sign_until(
+            keys,
+            time::OffsetDateTime::now_utc()
+                .unix_timestamp()
+                .saturating_add(cursor_ttl),
+        )
+    }
+
+    fn sign_until<K: Serialize>(keys: &K, expires_at: i64) -> Self {
+        use hmac::Mac;
+
+        let payload =
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&(keys, expires_at)).expect("Could not serialize cursor keys"));
+        let signature = URL_SAFE_NO_PAD.encode(cursor_mac().chain_update(&payload).finalize().into_bytes());
+
+        Self(format!("{payload}.{signature}"))
+    }
+
+    pub fn verify<K: DeserializeOwned>(&self) -> Result<K, CursorError> {
+        use hmac::Mac;
+
+        let (payload, signature) = self.0.split_once('.').ok_or(CursorError::Invalid)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| CursorError::Invalid)?;
+
+        cursor_mac()
+            .chain_update(payload)
+            .verify_slice(&signature)
+            .map_err(|_| CursorError::Invalid)?;
+
+        let payload = URL_SAFE_NO_PAD.decode(payload).map_err(|_| CursorError::Invalid)?;
+        let (keys, expires_at) = serde_json::from_slice::<(K, i64)>(&payload).map_err(|_| CursorError::Invalid)?;
+
+        if expires_at < time::OffsetDateTime::now_utc().unix_timestamp() {
+            return Err(CursorError::Expired);
+        }
+
+        Ok(keys)
+    }
+}
+
+// Cursors are signed with a key derived from SESSION_KEY, so it is never used directly for anything else.
+#[cfg(feature = "server")]
+fn cursor_mac() -> hmac::Hmac<sha2::Sha256> {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    use crate::config::SESSION_CONFIG;
+
+    static CURSOR_KEY: LazyLock<Vec<u8>> = LazyLock::new(|| {
+        Hmac::<Sha256>::new_from_slice(SESSION_CONFIG.key.as_bytes())
+            .expect("Could not derive cursor key")
+            .chain_update(b"dot-pagination-cursor")
+            .finalize()
+            .into_bytes()
+            .to_vec()
+    });
+
+    Hmac::new_from_slice(&CURSOR_KEY).expect("Could not create cursor signer")
+}
+
+macro_rules! impl_cursor_string {
+    ($cursor:ident, $validate:expr) => {
+        impl fmt::Display for $cursor {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $cursor {
+            type Err = CursorError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                if !value.is_empty() && $validate(value) {
+                    Ok(Self(value.to_owned()))
+                } else {
+                    Err(CursorError::Invalid)
+                }
+            }
+        }
+
+        impl Serialize for $cursor {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $cursor {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+fn is_base64(value: &str) -> bool {
+    URL_SAFE_NO_PAD.decode(value).is_ok()
+}
 
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+impl_cursor_string!(Cursor, is_base64);
+impl_cursor_string!(SignedCursor, |value: &str| value
+    .split_once('.')
+    .is_some_and(|(payload, signature)| is_base64(payload) && is_base64(signature)));
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CursorError {
+    Expired,
+    Invalid,
+    Missing,
+}
+
+impl fmt::Display for CursorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Expired => "Cursor has expired",
+            Self::Invalid => "Cursor is invalid",
+            Self::Missing => "Cursor page must have a cursor per node",
+        })
+    }
+}
+
+impl std::error::Error for CursorError {}
 
 #[derive(Clone, Deserialize, Serialize)]
-pub struct CursorPage<T> {
-    pub end_cursor: Option<Uuid>,
+pub struct CursorPage<T, C = Cursor> {
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub cursors: Vec<C>,
+    pub end_cursor: Option<C>,
     pub has_next_page: bool,
+    pub has_previous_page: bool,
     pub nodes: Vec<T>,
+    pub start_cursor: Option<C>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u64>,
 }
 
-impl<T> Default for CursorPage<T> {
+impl<T, C> Default for CursorPage<T, C> {
     fn default() -> Self {
         Self {
+            cursors: Vec::new(),
             end_cursor: None,
             has_next_page: false,
+            has_previous_page: false,
             nodes: Vec::new(),
+            start_cursor: None,
+            total_count: None,
+        }
+    }
+}
+
+impl<T, C> CursorPage<T, C> {
+    // The count is only awaited when asked for, as it is usually a separate and more expensive query.
+    pub async fn with_total_count<CT>(mut self, count: CT) -> Self
+    where
+        CT: Future<Output = u64>,
+    {
+        self.total_count = Some(count.await);
+        self
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Connection<T, C = Cursor> {
+    pub edges: Vec<Edge<T, C>>,
+    pub page_info: PageInfo<C>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u64>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Edge<T, C = Cursor> {
+    pub cursor: C,
+    pub node: T,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo<C = Cursor> {
+    pub end_cursor: Option<C>,
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<C>,
+}
+
+impl<T, C> Connection<T, C> {
+    fn new(cursor_page: CursorPage<T, C>) -> Self {
+        Self {
+            edges: cursor_page
+                .cursors
+                .into_iter()
+                .zip(cursor_page.nodes)
+                .map(|(cursor, node)| Edge { cursor, node })
+                .collect(),
+            page_info: PageInfo {
+                end_cursor: cursor_page.end_cursor,
+                has_next_page: cursor_page.has_next_page,
+                has_previous_page: cursor_page.has_previous_page,
+                start_cursor: cursor_page.start_cursor,
+            },
+            total_count: cursor_page.total_count,
+        }
+    }
+
+    // Pages built by hand or deserialized without `cursors` get the cursor of every edge from `cursor_fn`.
+    pub fn from_page_with<CF>(mut cursor_page: CursorPage<T, C>, cursor_fn: CF) -> Self
+    where
+        CF: Fn(&T) -> C,
+    {
+        cursor_page.cursors = cursor_page.nodes.iter().map(cursor_fn).collect();
+
+        Self::new(cursor_page)
+    }
+}
+
+impl<T, C> TryFrom<CursorPage<T, C>> for Connection<T, C> {
+    type Error = CursorError;
+
+    fn try_from(cursor_page: CursorPage<T, C>) -> Result<Self, Self::Error> {
+        if cursor_page.cursors.len() != cursor_page.nodes.len() {
+            return Err(CursorError::Missing);
         }
+
+        Ok(Self::new(cursor_page))
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CursorDirection {
+    Forward,
+    Backward,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PageSizeBounds {
+    pub default: u8,
+    pub max: u8,
+    pub min: u8,
+}
+
+impl PageSizeBounds {
+    pub fn global() -> Self {
+        #[cfg(feature = "server")]
+        {
+            use crate::config::PAGINATION_CONFIG;
+
+            Self {
+                default: PAGINATION_CONFIG.default_page_size,
+                max: PAGINATION_CONFIG.max_page_size,
+                min: PAGINATION_CONFIG.min_page_size,
+            }
+        }
+
+        #[cfg(not(feature = "server"))]
+        Self {
+            default: 10,
+            max: 100,
+            min: 1,
+        }
+    }
+
+    fn validate(&self, errors: &mut ValidationErrors, field: &'static str, page_size: u8) {
+        if !(self.min..=self.max).contains(&page_size) {
+            let mut error = ValidationError::new("range")
+                .with_message(format!("must be between {} and {}", self.min, self.max).into());
+
+            error.add_param("min".into(), &self.min);
+            error.add_param("max".into(), &self.max);
+            error.add_param("value".into(), &page_size);
+
+            errors.add(field, error);
+        }
+    }
+}
+
+fn default_page_size() -> u8 {
+    PageSizeBounds::global().default
+}
+
+pub trait BoundsProvider {
+    fn bounds() -> PageSizeBounds;
+}
+
+pub struct GlobalBounds;
+
+impl BoundsProvider for GlobalBounds {
+    fn bounds() -> PageSizeBounds {
+        PageSizeBounds::global()
+    }
+}
+
+pub trait PageParams {
+    fn validate_with(&self, bounds: &PageSizeBounds) -> Result<(), ValidationErrors>;
+
+    // Sets the page size left out by the client, which otherwise falls back to the global default.
+    fn fill_page_size(&mut self, default: u8);
+}
+
+fn validation_result(errors: ValidationErrors) -> Result<(), ValidationErrors> {
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
-pub struct CursorPageParams {
-    pub after: Option<Uuid>,
-    pub first: u8,
+pub struct CursorPageParams<C = Cursor> {
+    pub after: Option<C>,
+    pub before: Option<C>,
+    pub first: Option<u8>,
+    pub last: Option<u8>,
 }
 
-impl Default for CursorPageParams {
+impl<C> Default for CursorPageParams<C> {
     fn default() -> Self {
-        Self { after: None, first: 10 }
+        Self {
+            after: None,
+            before: None,
+            first: None,
+            last: None,
+        }
+    }
+}
+
+impl<C> PageParams for CursorPageParams<C> {
+    fn validate_with(&self, bounds: &PageSizeBounds) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        for (field, page_size) in [("first", self.first), ("last", self.last)] {
+            if let Some(page_size) = page_size {
+                bounds.validate(&mut errors, field, page_size);
+            }
+        }
+
+        for (field, is_conflict, message) in [
+            (
+                "before",
+                self.after.is_some() && self.before.is_some(),
+                "cannot be used together with after",
+            ),
+            (
+                "first",
+                self.before.is_some() && self.first.is_some(),
+                "cannot be used together with before",
+            ),
+            (
+                "last",
+                self.after.is_some() && self.last.is_some(),
+                "cannot be used together with after",
+            ),
+        ] {
+            if is_conflict {
+                errors.add(field, ValidationError::new("conflict").with_message(message.into()));
+            }
+        }
+
+        validation_result(errors)
+    }
+
+    fn fill_page_size(&mut self, default: u8) {
+        if self.first.is_none() && self.last.is_none() {
+            match self.direction() {
+                CursorDirection::Forward => self.first = Some(default),
+                CursorDirection::Backward => self.last = Some(default),
+            }
+        }
     }
 }
 
-impl CursorPageParams {
-    pub fn new(after: Option<Uuid>, first: u8) -> Self {
-        Self { after, first }
+impl<C> Validate for CursorPageParams<C> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.validate_with(&PageSizeBounds::global())
+    }
+}
+
+impl<C> CursorPageParams<C> {
+    pub fn new(after: Option<C>, first: u8) -> Self {
+        Self {
+            after,
+            first: Some(first),
+            ..Default::default()
+        }
+    }
+
+    pub fn new_before(before: Option<C>, last: u8) -> Self {
+        Self {
+            before,
+            last: Some(last),
+            ..Default::default()
+        }
+    }
+
+    pub fn cursor(&self) -> Option<&C> {
+        match self.direction() {
+            CursorDirection::Forward => self.after.as_ref(),
+            CursorDirection::Backward => self.before.as_ref(),
+        }
+    }
+
+    fn count(&self) -> u8 {
+        match self.direction() {
+            CursorDirection::Forward => self.first,
+            CursorDirection::Backward => self.last,
+        }
+        .unwrap_or_else(default_page_size)
+    }
+
+    #[cfg(feature = "server")]
+    fn limit(&self) -> i64 {
+        i64::from(self.count()) + 1
+    }
+
+    pub fn direction(&self) -> CursorDirection {
+        if self.before.is_some() || self.last.is_some() {
+            CursorDirection::Backward
+        } else {
+            CursorDirection::Forward
+        }
     }
 }
 
 #[cfg(feature = "server")]
-impl<T> CursorPage<T> {
-    pub async fn new<CT, CF, RT, RF, QF>(
-        cursor_page_params: &CursorPageParams,
+impl<T, C: Clone> CursorPage<T, C> {
+    // `cursor_resource_fn` resolves the cursor to what `query_fn` filters by, either a node or the decoded sort keys.
+    // In the backward direction `query_fn` returns the nodes before the cursor, nearest first.
+    pub async fn new<CR, CT, CF, RT, RF, QF>(
+        cursor_page_params: &CursorPageParams<C>,
         cursor_fn: CF,
         cursor_resource_fn: RF,
         query_fn: QF,
-    ) -> CursorPage<T>
+    ) -> CursorPage<T, C>
     where
-        CF: Fn(&T) -> Uuid,
-        CT: Future<Output = Option<T>>,
-        RF: Fn(Uuid) -> CT,
+        CF: Fn(&T) -> C,
+        CT: Future<Output = Option<CR>>,
+        RF: Fn(C) -> CT,
         RT: Future<Output = Vec<T>>,
-        QF: Fn(Option<T>, i64) -> RT,
+        QF: Fn(Option<CR>, i64, CursorDirection) -> RT,
     {
-        let cursor_resource = if let Some(after) = cursor_page_params.after {
-            cursor_resource_fn(after).await
+        let cursor_resource = if let Some(cursor) = cursor_page_params.cursor() {
+            cursor_resource_fn(cursor.clone()).await
         } else {
             None
         };
-        let limit = cursor_page_params.first + 1;
-        let mut nodes = query_fn(cursor_resource, limit.into()).await;
 
-        let has_next_page = if nodes.len() > cursor_page_params.first as usize {
-            nodes.remove(nodes.len() - 1);
+        Self::query(cursor_page_params, cursor_resource, cursor_fn, query_fn).await
+    }
+
+    async fn query<CR, CF, RT, QF>(
+        cursor_page_params: &CursorPageParams<C>,
+        cursor_resource: Option<CR>,
+        cursor_fn: CF,
+        query_fn: QF,
+    ) -> CursorPage<T, C>
+    where
+        CF: Fn(&T) -> C,
+        RT: Future<Output = Vec<T>>,
+        QF: Fn(Option<CR>, i64, CursorDirection) -> RT,
+    {
+        let has_cursor_resource = cursor_resource.is_some();
+        let nodes = query_fn(
+            cursor_resource,
+            cursor_page_params.limit(),
+            cursor_page_params.direction(),
+        )
+        .await;
+
+        Self::from_nodes(cursor_page_params, has_cursor_resource, nodes, cursor_fn)
+    }
+}
+
+impl<T, C: Clone> CursorPage<T, C> {
+    // `nodes` holds up to `count()` + 1 rows, the extra one only tells whether there is another page.
+    fn from_nodes<CF>(
+        cursor_page_params: &CursorPageParams<C>,
+        has_cursor_resource: bool,
+        mut nodes: Vec<T>,
+        cursor_fn: CF,
+    ) -> CursorPage<T, C>
+    where
+        CF: Fn(&T) -> C,
+    {
+        let direction = cursor_page_params.direction();
+        let has_more = if nodes.len() > cursor_page_params.count() as usize {
+            nodes.truncate(cursor_page_params.count() as usize);
 
             true
         } else {
             false
         };
 
-        let end_cursor = nodes.last().map(|n| cursor_fn(n));
+        let (has_next_page, has_previous_page) = match direction {
+            CursorDirection::Forward => (has_more, has_cursor_resource),
+            CursorDirection::Backward => {
+                nodes.reverse();
+
+                (has_cursor_resource, has_more)
+            }
+        };
+
+        let cursors = nodes.iter().map(cursor_fn).collect::<Vec<_>>();
 
         Self {
-            end_cursor,
-            nodes,
+            end_cursor: cursors.last().cloned(),
             has_next_page,
+            has_previous_page,
+            nodes,
+            start_cursor: cursors.first().cloned(),
+            total_count: None,
+            cursors,
+        }
+    }
+}
+
+impl<T> CursorPage<T> {
+    // Paginates nodes already sorted by their keys, with cursors encoded like those built from `keys_fn` elsewhere.
+    pub fn from_slice<K, KF>(
+        cursor_page_params: &CursorPageParams,
+        nodes: &[T],
+        keys_fn: KF,
+    ) -> Result<CursorPage<T>, CursorError>
+    where
+        K: DeserializeOwned + Ord + Serialize,
+        KF: Fn(&T) -> K,
+        T: Clone,
+    {
+        let (has_cursor, range) = Self::range(cursor_page_params, nodes, &keys_fn)?;
+
+        Ok(Self::from_range(
+            cursor_page_params,
+            has_cursor,
+            nodes[range].to_vec(),
+            keys_fn,
+        ))
+    }
+
+    pub fn from_iter<I, K, KF>(
+        cursor_page_params: &CursorPageParams,
+        nodes: I,
+        keys_fn: KF,
+    ) -> Result<CursorPage<T>, CursorError>
+    where
+        I: IntoIterator<Item = T>,
+        K: DeserializeOwned + Ord + Serialize,
+        KF: Fn(&T) -> K,
+    {
+        let mut nodes = nodes.into_iter().collect::<Vec<_>>();
+        let (has_cursor, range) = Self::range(cursor_page_params, &nodes, &keys_fn)?;
+
+        Ok(Self::from_range(
+            cursor_page_params,
+            has_cursor,
+            nodes.drain(range).collect(),
+            keys_fn,
+        ))
+    }
+
+    // The nodes `query_fn` would return, in ascending order, so the cursor node does not need to exist anymore.
+    fn range<K, KF>(
+        cursor_page_params: &CursorPageParams,
+        nodes: &[T],
+        keys_fn: &KF,
+    ) -> Result<(bool, Range<usize>), CursorError>
+    where
+        K: DeserializeOwned + Ord,
+        KF: Fn(&T) -> K,
+    {
+        let keys = cursor_page_params.cursor().map(Cursor::decode::<K>).transpose()?;
+        let limit = cursor_page_params.count() as usize + 1;
+
+        let range = match cursor_page_params.direction() {
+            CursorDirection::Forward => {
+                let start = keys
+                    .as_ref()
+                    .map_or(0, |keys| nodes.partition_point(|node| keys_fn(node) <= *keys));
+
+                start..nodes.len().min(start + limit)
+            }
+            CursorDirection::Backward => {
+                let end = keys
+                    .as_ref()
+                    .map_or(nodes.len(), |keys| nodes.partition_point(|node| keys_fn(node) < *keys));
+
+                end.saturating_sub(limit)..end
+            }
+        };
+
+        Ok((keys.is_some(), range))
+    }
+
+    fn from_range<K, KF>(
+        cursor_page_params: &CursorPageParams,
+        has_cursor: bool,
+        mut nodes: Vec<T>,
+        keys_fn: KF,
+    ) -> CursorPage<T>
+    where
+        K: Serialize,
+        KF: Fn(&T) -> K,
+    {
+        if cursor_page_params.direction() == CursorDirection::Backward {
+            nodes.reverse();
+        }
+
+        Self::from_nodes(cursor_page_params, has_cursor, nodes, |node| {
+            Cursor::encode(&keys_fn(node))
+        })
+    }
+}
+
+#[cfg(feature = "server")]
+impl<T> CursorPage<T, SignedCursor> {
+    // Like `new`, but the cursor is verified and `cursor_resource_fn` receives the signed keys.
+    pub async fn new_signed<K, CR, CT, KF, RT, RF, QF>(
+        cursor_page_params: &CursorPageParams<SignedCursor>,
+        keys_fn: KF,
+        cursor_resource_fn: RF,
+        query_fn: QF,
+    ) -> Result<CursorPage<T, SignedCursor>, CursorError>
+    where
+        K: Serialize + DeserializeOwned,
+        KF: Fn(&T) -> K,
+        CT: Future<Output = Option<CR>>,
+        RF: Fn(K) -> CT,
+        RT: Future<Output = Vec<T>>,
+        QF: Fn(Option<CR>, i64, CursorDirection) -> RT,
+    {
+        let cursor_resource = match cursor_page_params.cursor() {
+            Some(cursor) => cursor_resource_fn(cursor.verify()?).await,
+            None => None,
+        };
+
+        Ok(Self::query(
+            cursor_page_params,
+            cursor_resource,
+            |node| SignedCursor::sign(&keys_fn(node)),
+            query_fn,
+        )
+        .await)
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct OffsetPage<T> {
+    pub nodes: Vec<T>,
+    pub page: u32,
+    pub per_page: u8,
+    pub total_count: u64,
+    pub total_pages: u32,
+}
+
+impl<T> Default for OffsetPage<T> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            page: 1,
+            per_page: 10,
+            total_count: 0,
+            total_pages: 0,
+        }
+    }
+}
+
+impl<T> OffsetPage<T> {
+    pub fn has_next_page(&self) -> bool {
+        self.page < self.total_pages
+    }
+
+    pub fn has_previous_page(&self) -> bool {
+        self.page > 1
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct OffsetPageParams {
+    #[serde(default = "default_page")]
+    pub page: u32,
+    pub per_page: Option<u8>,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+impl Default for OffsetPageParams {
+    fn default() -> Self {
+        Self {
+            page: default_page(),
+            per_page: None,
+        }
+    }
+}
+
+impl PageParams for OffsetPageParams {
+    fn validate_with(&self, bounds: &PageSizeBounds) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(per_page) = self.per_page {
+            bounds.validate(&mut errors, "per_page", per_page);
+        }
+
+        if self.page == 0 {
+            errors.add(
+                "page",
+                ValidationError::new("range").with_message("must be at least 1".into()),
+            );
+        }
+
+        validation_result(errors)
+    }
+
+    fn fill_page_size(&mut self, default: u8) {
+        self.per_page.get_or_insert(default);
+    }
+}
+
+impl Validate for OffsetPageParams {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.validate_with(&PageSizeBounds::global())
+    }
+}
+
+impl OffsetPageParams {
+    pub fn new(page: u32, per_page: u8) -> Self {
+        Self {
+            page,
+            per_page: Some(per_page),
         }
     }
+
+    pub fn per_page(&self) -> u8 {
+        self.per_page.unwrap_or_else(default_page_size)
+    }
+
+    pub fn offset(&self) -> i64 {
+        i64::from(self.page.max(1) - 1) * i64::from(self.per_page())
+    }
+
+    fn range(&self, len: usize) -> Range<usize> {
+        let start = usize::try_from(self.offset()).unwrap_or(usize::MAX).min(len);
+
+        start..len.min(start.saturating_add(self.per_page().into()))
+    }
+}
+
+#[cfg(feature = "server")]
+impl<T> OffsetPage<T> {
+    pub async fn new<CT, RT, QF>(offset_page_params: &OffsetPageParams, count: CT, query_fn: QF) -> OffsetPage<T>
+    where
+        CT: Future<Output = u64>,
+        RT: Future<Output = Vec<T>>,
+        QF: Fn(i64, i64) -> RT,
+    {
+        let total_count = count.await;
+        let offset = offset_page_params.offset();
+        let nodes = if offset_page_params.per_page() == 0 || offset as u64 >= total_count {
+            Vec::new()
+        } else {
+            query_fn(offset, offset_page_params.per_page().into()).await
+        };
+
+        Self::from_nodes(offset_page_params, total_count, nodes)
+    }
+}
+
+impl<T> OffsetPage<T> {
+    pub fn from_slice(offset_page_params: &OffsetPageParams, nodes: &[T]) -> OffsetPage<T>
+    where
+        T: Clone,
+    {
+        let page_nodes = nodes[offset_page_params.range(nodes.len())].to_vec();
+
+        Self::from_nodes(offset_page_params, nodes.len() as u64, page_nodes)
+    }
+
+    // Only the nodes in the page are kept, the rest are just counted.
+    pub fn from_iter<I>(offset_page_params: &OffsetPageParams, nodes: I) -> OffsetPage<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut total_count = 0;
+        let range = offset_page_params.range(usize::MAX);
+        let page_nodes = nodes
+            .into_iter()
+            .inspect(|_| total_count += 1)
+            .enumerate()
+            .filter_map(|(index, node)| range.contains(&index).then_some(node))
+            .collect();
+
+        Self::from_nodes(offset_page_params, total_count, page_nodes)
+    }
+
+    fn from_nodes(offset_page_params: &OffsetPageParams, total_count: u64, nodes: Vec<T>) -> OffsetPage<T> {
+        let per_page = offset_page_params.per_page();
+        let total_pages = match per_page {
+            0 => 0,
+            per_page => total_count.div_ceil(per_page.into()).try_into().unwrap_or(u32::MAX),
+        };
+
+        Self {
+            nodes,
+            page: offset_page_params.page.max(1),
+            per_page,
+            total_count,
+            total_pages,
+        }
+    }
+}
+
+// The page size bounds default to the global ones, a type implementing `BoundsProvider` sets them per route.
+#[cfg(feature = "server")]
+pub struct PageQuery<P, B = GlobalBounds>(pub P, pub PhantomData<B>);
+
+#[cfg(feature = "server")]
+impl<P, B, S> axum::extract::FromRequestParts<S> for PageQuery<P, B>
+where
+    P: DeserializeOwned + PageParams,
+    B: BoundsProvider,
+    S: Send + Sync,
+{
+    type Rejection = PageQueryRejection;
+
+    async fn from_request_parts(parts: &mut axum::http::request::Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let bounds = B::bounds();
+        let axum::extract::Query(mut params) = axum::extract::Query::<P>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| PageQueryRejection {
+                message: rejection.body_text(),
+                errors: ValidationErrors::new(),
+            })?;
+
+        params.validate_with(&bounds).map_err(|errors| PageQueryRejection {
+            message: "Invalid pagination parameters".to_owned(),
+            errors,
+        })?;
+        params.fill_page_size(bounds.default);
+
+        Ok(Self(params, PhantomData))
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Serialize)]
+pub struct PageQueryRejection {
+    pub message: String,
+    pub errors: ValidationErrors,
+}
+
+#[cfg(feature = "server")]
+impl axum::response::IntoResponse for PageQueryRejection {
+    fn into_response(self) -> axum::response::Response {
+        (axum::http::StatusCode::BAD_REQUEST, axum::Json(self)).into_response()
+    }
+}
+
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+pub trait SortKeys<'q, DB: sqlx::Database>: DeserializeOwned + Serialize + Send + 'q {
+    // Adds every key to `arguments`, writing their placeholders separated by commas to `sql`.
+    fn bind(self, sql: &mut String, arguments: &mut DB::Arguments<'q>) -> Result<(), sqlx::error::BoxDynError>;
+}
+
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+macro_rules! impl_sort_keys {
+    ($first:ident $(, $rest:ident)*) => {
+        impl<'q, DB, $first $(, $rest)*> SortKeys<'q, DB> for ($first, $($rest,)*)
+        where
+            DB: sqlx::Database,
+            $first: DeserializeOwned + Serialize + sqlx::Encode<'q, DB> + sqlx::Type<DB> + Send + 'q,
+            $($rest: DeserializeOwned + Serialize + sqlx::Encode<'q, DB> + sqlx::Type<DB> + Send + 'q,)*
+        {
+            #[allow(non_snake_case)]
+            fn bind(self, sql: &mut String, arguments: &mut DB::Arguments<'q>) -> Result<(), sqlx::error::BoxDynError> {
+                use sqlx::Arguments;
+
+                let ($first, $($rest,)*) = self;
+
+                arguments.add($first)?;
+                arguments.format_placeholder(sql)?;
+                $(
+                    sql.push_str(", ");
+                    arguments.add($rest)?;
+                    arguments.format_placeholder(sql)?;
+                )*
+
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+impl_sort_keys!(A);
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+impl_sort_keys!(A, B);
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+impl_sort_keys!(A, B, C);
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+impl_sort_keys!(A, B, C, D);
+
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+#[derive(Clone, Copy, Debug)]
+pub struct KeysetQuery<'a> {
+    base_query: &'a str,
+    order: SortOrder,
+    sort_columns: &'a [&'a str],
+}
+
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+impl<'a> KeysetQuery<'a> {
+    // `base_query` is wrapped as a subquery, so it can filter and join freely but must select every sort column,
+    // and the sort columns must end with a unique one (usually `id`) for the order to be total.
+    pub fn new(base_query: &'a str, sort_columns: &'a [&'a str]) -> Self {
+        Self {
+            base_query,
+            order: SortOrder::default(),
+            sort_columns,
+        }
+    }
+
+    pub fn order(mut self, order: SortOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    // `keys_fn` returns the values of the sort columns for a row, in the same order, which become its cursor.
+    pub async fn fetch<'e, DB, E, K, KF, T>(
+        &self,
+        executor: E,
+        cursor_page_params: &CursorPageParams,
+        keys_fn: KF,
+    ) -> Result<CursorPage<T>, sqlx::Error>
+    where
+        DB: sqlx::Database,
+        E: sqlx::Executor<'e, Database = DB>,
+        K: for<'q> SortKeys<'q, DB>,
+        KF: Fn(&T) -> K,
+        T: for<'r> sqlx::FromRow<'r, DB::Row> + Send + Unpin,
+        i64: for<'q> sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+        for<'q> DB::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
+    {
+        use sqlx::Arguments;
+
+        let keys = cursor_page_params
+            .cursor()
+            .map(Cursor::decode::<K>)
+            .transpose()
+            .map_err(|error| sqlx::Error::Decode(Box::new(error)))?;
+        let has_keys = keys.is_some();
+
+        let (operator, order) = match (self.order, cursor_page_params.direction()) {
+            (SortOrder::Ascending, CursorDirection::Forward) | (SortOrder::Descending, CursorDirection::Backward) => {
+                (">", "ASC")
+            }
+            _ => ("<", "DESC"),
+        };
+
+        let mut sql = format!("SELECT * FROM ({}) AS keyset_page", self.base_query);
+        let mut arguments = DB::Arguments::default();
+
+        if let Some(keys) = keys {
+            sql.push_str(&format!(" WHERE ({}) {operator} (", self.sort_columns.join(", ")));
+            keys.bind(&mut sql, &mut arguments).map_err(sqlx::Error::Encode)?;
+            sql.push(')');
+        }
+
+        let order_by = self
+            .sort_columns
+            .iter()
+            .map(|column| format!("{column} {order}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        sql.push_str(&format!(" ORDER BY {order_by} LIMIT "));
+        arguments.add(cursor_page_params.limit()).map_err(sqlx::Error::Encode)?;
+        arguments
+            .format_placeholder(&mut sql)
+            .map_err(|error| sqlx::Error::Encode(error.into()))?;
+
+        let nodes = sqlx::query_as_with::<DB, T, _>(&sql, arguments)
+            .fetch_all(executor)
+            .await?;
+
+        Ok(CursorPage::from_nodes(cursor_page_params, has_keys, nodes, |node| {
+            Cursor::encode(&keys_fn(node))
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use axum::extract::FromRequestParts;
+    use axum::http::Request;
+
+    use super::*;
+
+    struct SmallPages;
+
+    impl BoundsProvider for SmallPages {
+        fn bounds() -> PageSizeBounds {
+            PageSizeBounds {
+                default: 3,
+                max: 5,
+                min: 2,
+            }
+        }
+    }
+
+    async fn page_query<P, B>(query: &str) -> Result<P, PageQueryRejection>
+    where
+        P: DeserializeOwned + PageParams,
+        B: BoundsProvider,
+    {
+        let (mut parts, _) = Request::get(format!("/?{query}")).body(()).unwrap().into_parts();
+        let PageQuery(params, _) = PageQuery::<P, B>::from_request_parts(&mut parts, &()).await?;
+
+        Ok(params)
+    }
+
+    #[tokio::test]
+    async fn page_queries_use_the_given_bounds() {
+        let params = page_query::<CursorPageParams, SmallPages>("").await.unwrap();
+
+        assert_eq!(params.first, Some(3));
+
+        let params = page_query::<CursorPageParams, SmallPages>("before=Mw").await.unwrap();
+
+        assert_eq!((params.first, params.last), (None, Some(3)));
+
+        let params = page_query::<OffsetPageParams, SmallPages>("page=2").await.unwrap();
+
+        assert_eq!(params.offset(), 3);
+
+        let rejection = page_query::<CursorPageParams, SmallPages>("first=10")
+            .await
+            .unwrap_err();
+
+        assert!(rejection.errors.field_errors().contains_key("first"));
+        assert!(page_query::<OffsetPageParams, SmallPages>("per_page=1").await.is_err());
+        assert!(page_query::<CursorPageParams, GlobalBounds>("first=10").await.is_ok());
+    }
+
+    fn parts(cursor: &SignedCursor) -> (&str, &str) {
+        cursor.0.split_once('.').unwrap()
+    }
+
+    #[test]
+    fn signed_cursors_round_trip() {
+        let cursor = SignedCursor::sign(&(5, "e"));
+
+        assert_eq!(cursor.verify::<(u32, String)>(), Ok((5, "e".to_owned())));
+        assert_eq!(cursor.to_string().parse::<SignedCursor>(), Ok(cursor));
+    }
+
+    #[test]
+    fn signed_cursors_reject_a_changed_payload() {
+        let cursor = SignedCursor::sign(&5);
+        let other = SignedCursor::sign(&6);
+        let changed = SignedCursor(format!("{}.{}", parts(&other).0, parts(&cursor).1));
+
+        assert_eq!(changed.verify::<u32>(), Err(CursorError::Invalid));
+        assert_eq!(
+            Cursor::encode(&6).to_string().parse::<SignedCursor>(),
+            Err(CursorError::Invalid)
+        );
+    }
+
+    #[test]
+    fn signed_cursors_reject_a_changed_signature() {
+        let cursor = SignedCursor::sign(&5);
+        let (payload, signature) = parts(&cursor);
+        let mut signature = URL_SAFE_NO_PAD.decode(signature).unwrap();
+
+        signature[0] ^= 1;
+
+        let changed = SignedCursor(format!("{payload}.{}", URL_SAFE_NO_PAD.encode(signature)));
+
+        assert_eq!(changed.verify::<u32>(), Err(CursorError::Invalid));
+        assert_eq!(
+            SignedCursor(format!("{payload}.")).verify::<u32>(),
+            Err(CursorError::Invalid)
+        );
+    }
+
+    #[test]
+    fn signed_cursors_expire() {
+        let now = time::OffsetDateTime::now_utc().unix_timestamp();
+
+        assert_eq!(
+            SignedCursor::sign_until(&5, now - 1).verify::<u32>(),
+            Err(CursorError::Expired)
+        );
+        assert_eq!(SignedCursor::sign_until(&5, now + 60).verify::<u32>(), Ok(5));
+        assert_eq!(SignedCursor::sign_until(&5, i64::MAX).verify::<u32>(), Ok(5));
+    }
 }
@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use tower_sessions::SessionStore;
+use tower_sessions::session::{Id, Record};
+use tower_sessions::session_store::Result;
+
+use crate::config::{SessionConfig, SessionStoreKind};
+
+#[cfg(not(any(
+    feature = "session-file",
+    feature = "session-memory",
+    feature = "session-redis",
+    feature = "session-sqlite"
+)))]
+compile_error!("At least one of `session-file`, `session-memory`, `session-redis` or `session-sqlite` must be enabled");
+
+macro_rules! dispatch {
+    ($self:ident, $store:ident => $expr:expr) => {
+        match *$self {
+            #[cfg(feature = "session-file")]
+            Self::File(ref $store) => $expr,
+            #[cfg(feature = "session-memory")]
+            Self::Memory(ref $store) => $expr,
+            #[cfg(feature = "session-redis")]
+            Self::Redis(ref $store, _) => $expr,
+            #[cfg(feature = "session-sqlite")]
+            Self::Sqlite(ref $store) => $expr,
+        }
+    };
+}
+
+#[derive(Clone, Debug)]
+pub enum DotSessionStore {
+    #[cfg(feature = "session-file")]
+    File(tower_sessions_file_store::FileSessionStorage),
+    #[cfg(feature = "session-memory")]
+    Memory(tower_sessions::MemoryStore),
+    #[cfg(feature = "session-redis")]
+    Redis(
+        tower_sessions_redis_store::RedisStore<fred::prelude::Pool>,
+        fred::prelude::Pool,
+    ),
+    #[cfg(feature = "session-sqlite")]
+    Sqlite(tower_sessions_sqlx_store::SqliteStore),
+}
+
+impl DotSessionStore {
+    #[cfg_attr(
+        not(any(feature = "session-redis", feature = "session-sqlite")),
+        allow(unused_variables)
+    )]
+    pub(crate) async fn connect(config: &SessionConfig, pool_size: usize) -> anyhow::Result<Self> {
+        match config.store() {
+            #[cfg(feature = "session-file")]
+            Some(SessionStoreKind::File) => {
+                use tower_sessions::ExpiredDeletion;
+                use tower_sessions_file_store::FileSessionStorage;
+
+                let store = FileSessionStorage::new_in_folder(std::path::PathBuf::from(&config.file_path));
+
+                tokio::spawn(
+                    store
+                        .clone()
+                        .continuously_delete_expired(tokio::time::Duration::from_secs(60)),
+                );
+
+                Ok(Self::File(store))
+            }
+            #[cfg(feature = "session-memory")]
+            Some(SessionStoreKind::Memory) => Ok(Self::Memory(tower_sessions::MemoryStore::default())),
+            #[cfg(feature = "session-redis")]
+            Some(SessionStoreKind::Redis) => {
+                use fred::prelude::{ClientLike, Config, Pool};
+                use tower_sessions_redis_store::RedisStore;
+
+                let pool = Pool::new(Config::from_url(&config.redis_url)?, None, None, None, pool_size)?;
+
+                pool.connect();
+                pool.wait_for_connect().await?;
+
+                Ok(Self::Redis(RedisStore::new(pool.clone()), pool))
+            }
+            #[cfg(feature = "session-sqlite")]
+            Some(SessionStoreKind::Sqlite) => {
+                use tower_sessions::ExpiredDeletion;
+                use tower_sessions_sqlx_store::SqliteStore;
+                use tower_sessions_sqlx_store::sqlx::sqlite::SqlitePoolOptions;
+
+                let pool = SqlitePoolOptions::new()
+                    .max_connections(pool_size as u32)
+                    .connect(&config.sqlite_url)
+                    .await?;
+                let store = SqliteStore::new(pool);
+
+                store.migrate().await?;
+
+                tokio::spawn(
+                    store
+                        .clone()
+                        .continuously_delete_expired(tokio::time::Duration::from_secs(60)),
+                );
+
+                Ok(Self::Sqlite(store))
+            }
+            #[allow(unreachable_patterns)]
+            Some(kind) => anyhow::bail!("Session store `{kind}` is not enabled, check the `session-{kind}` feature"),
+            None => anyhow::bail!("Session store is not set, check SESSION_STORE"),
+        }
+    }
+
+    pub(crate) async fn close(&self) -> anyhow::Result<()> {
+        #[cfg(feature = "session-redis")]
+        #[allow(irrefutable_let_patterns)]
+        if let Self::Redis(_, pool) = self {
+            use fred::prelude::ClientLike;
+
+            pool.quit().await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SessionStore for DotSessionStore {
+    async fn create(&self, record: &mut Record) -> Result<()> {
+        dispatch!(self, store => store.create(record).await)
+    }
+
+    async fn save(&self, record: &Record) -> Result<()> {
+        dispatch!(self, store => store.save(record).await)
+    }
+
+    async fn load(&self, session_id: &Id) -> Result<Option<Record>> {
+        dispatch!(self, store => store.load(session_id).await)
+    }
+
+    async fn delete(&self, session_id: &Id) -> Result<()> {
+        dispatch!(self, store => store.delete(session_id).await)
+    }
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "sqlx-sqlite")]
+
+use dot::pagination::{Cursor, CursorError, CursorPage, CursorPageParams, KeysetQuery, SortOrder};
+use sqlx::{Connection, SqliteConnection};
+
+type Post = (i64, String, String);
+
+const POSTS_QUERY: &str = "SELECT id, created_at, title FROM posts WHERE title != 'draft'";
+
+async fn connect() -> SqliteConnection {
+    let mut connection = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+
+    sqlx::query("CREATE TABLE posts (id INTEGER PRIMARY KEY, created_at TEXT NOT NULL, title TEXT NOT NULL)")
+        .execute(&mut connection)
+        .await
+        .unwrap();
+
+    for (id, created_at, title) in [
+        (1, "2025-01-01", "a"),
+        (2, "2025-01-02", "b"),
+        (3, "2025-01-02", "c"),
+        (4, "2025-01-02", "draft"),
+        (5, "2025-01-02", "d"),
+        (6, "2025-01-03", "e"),
+        (7, "2025-01-04", "f"),
+    ] {
+        sqlx::query("INSERT INTO posts (id, created_at, title) VALUES (?, ?, ?)")
+            .bind(id)
+            .bind(created_at)
+            .bind(title)
+            .execute(&mut connection)
+            .await
+            .unwrap();
+    }
+
+    connection
+}
+
+async fn fetch(
+    connection: &mut SqliteConnection,
+    order: SortOrder,
+    cursor_page_params: &CursorPageParams,
+) -> CursorPage<Post> {
+    KeysetQuery::new(POSTS_QUERY, &["created_at", "id"])
+        .order(order)
+        .fetch(connection, cursor_page_params, |(id, created_at, _): &Post| {
+            (created_at.clone(), *id)
+        })
+        .await
+        .unwrap()
+}
+
+fn ids(cursor_page: &CursorPage<Post>) -> Vec<i64> {
+    cursor_page.nodes.iter().map(|(id, _, _)| *id).collect()
+}
+
+#[tokio::test]
+async fn keyset_query_pages_forward_and_backward() {
+    let mut connection = connect().await;
+
+    let first = fetch(&mut connection, SortOrder::Ascending, &CursorPageParams::new(None, 4)).await;
+
+    assert_eq!(ids(&first), [1, 2, 3, 5]);
+    assert!(first.has_next_page && !first.has_previous_page);
+
+    let second = fetch(
+        &mut connection,
+        SortOrder::Ascending,
+        &CursorPageParams::new(first.end_cursor.clone(), 4),
+    )
+    .await;
+
+    assert_eq!(ids(&second), [6, 7]);
+    assert!(!second.has_next_page && second.has_previous_page);
+
+    let previous = fetch(
+        &mut connection,
+        SortOrder::Ascending,
+        &CursorPageParams::new_before(second.start_cursor.clone(), 3),
+    )
+    .await;
+
+    assert_eq!(ids(&previous), [2, 3, 5]);
+    assert!(previous.has_next_page && previous.has_previous_page);
+    assert_eq!(previous.cursors.len(), previous.nodes.len());
+}
+
+#[tokio::test]
+async fn keyset_query_pages_in_descending_order() {
+    let mut connection = connect().await;
+
+    let first = fetch(&mut connection, SortOrder::Descending, &CursorPageParams::new(None, 3)).await;
+
+    assert_eq!(ids(&first), [7, 6, 5]);
+
+    let second = fetch(
+        &mut connection,
+        SortOrder::Descending,
+        &CursorPageParams::new(first.end_cursor.clone(), 3),
+    )
+    .await;
+
+    assert_eq!(ids(&second), [3, 2, 1]);
+    assert!(!second.has_next_page && second.has_previous_page);
+
+    let previous = fetch(
+        &mut connection,
+        SortOrder::Descending,
+        &CursorPageParams::new_before(second.start_cursor.clone(), 3),
+    )
+    .await;
+
+    assert_eq!(ids(&previous), [7, 6, 5]);
+    assert!(!previous.has_previous_page);
+}
+
+#[tokio::test]
+async fn keyset_query_rejects_invalid_cursors() {
+    let mut connection = connect().await;
+
+    for cursor in [Cursor::encode(&"2025-01-02"), "e30".parse().unwrap()] {
+        let error = KeysetQuery::new(POSTS_QUERY, &["created_at", "id"])
+            .fetch(
+                &mut connection,
+                &CursorPageParams::new(Some(cursor), 3),
+                |(id, created_at, _): &Post| (created_at.clone(), *id),
+            )
+            .await
+            .err()
+            .unwrap();
+
+        assert!(
+            matches!(&error, sqlx::Error::Decode(source) if source.downcast_ref() == Some(&CursorError::Invalid)),
+            "{error:?}"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+fn defined_keys(dir: &Path) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+
+        if path.extension().is_some_and(|extension| extension == "ftl") {
+            for line in fs::read_to_string(&path).unwrap().lines() {
+                if let Some((key, _)) = line.split_once('=')
+                    && !line.starts_with([' ', '#', '.'])
+                {
+                    keys.insert(key.trim().to_owned());
+                }
+            }
+        }
+    }
+
+    keys
+}
+
+#[test]
+fn all_locales_define_the_used_keys() {
+    let used = dot::i18n::used_keys(Path::new("src")).unwrap();
+
+    assert!(!used.is_empty());
+
+    for entry in fs::read_dir("locales").unwrap() {
+        let path = entry.unwrap().path();
+
+        if path.is_dir() {
+            let missing = used.difference(&defined_keys(&path)).cloned().collect::<Vec<_>>();
+
+            assert!(missing.is_empty(), "{} is missing {missing:?}", path.display());
+        }
+    }
+}
+
+#[test]
+fn all_locales_are_listed_in_languages_yaml() {
+    let languages = fs::read_to_string("locales/languages.yaml").unwrap();
+
+    for entry in fs::read_dir("locales").unwrap() {
+        let path = entry.unwrap().path();
+
+        if path.is_dir() {
+            let locale = path.file_name().unwrap().to_str().unwrap();
+
+            assert!(
+                languages.lines().any(|line| line == format!("- - {locale}")),
+                "{locale} is not listed in languages.yaml"
+            );
+        }
+    }
+}
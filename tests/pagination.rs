@@ -0,0 +1,227 @@
+use dot::pagination::{
+    Connection, Cursor, CursorError, CursorPage, CursorPageParams, OffsetPage, OffsetPageParams, PageParams,
+    PageSizeBounds,
+};
+
+fn ids(cursor_page: &CursorPage<(u32, &str)>) -> Vec<u32> {
+    cursor_page.nodes.iter().map(|(id, _)| *id).collect()
+}
+
+fn keys(node: &(u32, &str)) -> u32 {
+    node.0
+}
+
+const NODES: [(u32, &str); 7] = [(1, "a"), (2, "b"), (3, "c"), (5, "d"), (8, "e"), (13, "f"), (21, "g")];
+
+#[test]
+fn cursor_pages_walk_forward_and_backward() {
+    let first = CursorPage::from_slice(&CursorPageParams::new(None, 3), &NODES, keys).unwrap();
+
+    assert_eq!(ids(&first), [1, 2, 3]);
+    assert!(first.has_next_page && !first.has_previous_page);
+    assert_eq!(first.end_cursor, Some(Cursor::encode(&3)));
+
+    let second = CursorPage::from_slice(&CursorPageParams::new(first.end_cursor.clone(), 3), &NODES, keys).unwrap();
+
+    assert_eq!(ids(&second), [5, 8, 13]);
+    assert!(second.has_next_page && second.has_previous_page);
+
+    let third = CursorPage::from_slice(&CursorPageParams::new(second.end_cursor.clone(), 3), &NODES, keys).unwrap();
+
+    assert_eq!(ids(&third), [21]);
+    assert!(!third.has_next_page && third.has_previous_page);
+
+    let previous = CursorPage::from_slice(
+        &CursorPageParams::new_before(third.start_cursor.clone(), 2),
+        &NODES,
+        keys,
+    )
+    .unwrap();
+
+    assert_eq!(ids(&previous), [8, 13]);
+    assert!(previous.has_next_page && previous.has_previous_page);
+
+    let last = CursorPage::from_slice(&CursorPageParams::new_before(None, 2), &NODES, keys).unwrap();
+
+    assert_eq!(ids(&last), [13, 21]);
+    assert!(!last.has_next_page && last.has_previous_page);
+}
+
+#[test]
+fn cursor_pages_from_iterators_match_slices() {
+    for cursor_page_params in [
+        CursorPageParams::new(None, 4),
+        CursorPageParams::new(Some(Cursor::encode(&5)), 4),
+        CursorPageParams::new_before(Some(Cursor::encode(&5)), 2),
+        CursorPageParams::new(Some(Cursor::encode(&4)), 2),
+    ] {
+        let from_slice = CursorPage::from_slice(&cursor_page_params, &NODES, keys).unwrap();
+        let from_iter = CursorPage::from_iter(&cursor_page_params, NODES, keys).unwrap();
+
+        assert_eq!(ids(&from_slice), ids(&from_iter));
+        assert_eq!(from_slice.cursors, from_iter.cursors);
+        assert_eq!(from_slice.has_next_page, from_iter.has_next_page);
+        assert_eq!(from_slice.has_previous_page, from_iter.has_previous_page);
+    }
+}
+
+#[test]
+fn cursor_pages_continue_from_removed_cursor_nodes() {
+    let after = CursorPage::from_iter(&CursorPageParams::new(Some(Cursor::encode(&4)), 2), NODES, keys).unwrap();
+
+    assert_eq!(ids(&after), [5, 8]);
+    assert!(after.has_next_page && after.has_previous_page);
+
+    let before =
+        CursorPage::from_slice(&CursorPageParams::new_before(Some(Cursor::encode(&4)), 2), &NODES, keys).unwrap();
+
+    assert_eq!(ids(&before), [2, 3]);
+    assert!(before.has_next_page && before.has_previous_page);
+
+    let past_the_end =
+        CursorPage::from_slice(&CursorPageParams::new(Some(Cursor::encode(&34)), 2), &NODES, keys).unwrap();
+
+    assert!(past_the_end.nodes.is_empty());
+    assert!(!past_the_end.has_next_page && past_the_end.has_previous_page);
+}
+
+#[test]
+fn cursor_pages_reject_invalid_cursors() {
+    let cursor_page_params = CursorPageParams::new(Some(Cursor::encode(&"e")), 2);
+
+    assert_eq!(
+        CursorPage::from_slice(&cursor_page_params, &NODES, keys).err(),
+        Some(CursorError::Invalid)
+    );
+    assert_eq!(
+        CursorPage::from_iter(&cursor_page_params, NODES, keys).err(),
+        Some(CursorError::Invalid)
+    );
+}
+
+#[test]
+fn connections_have_an_edge_per_node() {
+    let cursor_page = CursorPage::from_slice(&CursorPageParams::new(None, 2), &NODES, keys).unwrap();
+    let connection = Connection::try_from(cursor_page.clone()).unwrap();
+
+    assert_eq!(
+        connection
+            .edges
+            .iter()
+            .map(|edge| (edge.cursor.clone(), edge.node.0))
+            .collect::<Vec<_>>(),
+        [(Cursor::encode(&1), 1), (Cursor::encode(&2), 2)]
+    );
+    assert_eq!(connection.page_info.end_cursor, cursor_page.end_cursor);
+    assert!(connection.page_info.has_next_page);
+
+    let hand_built = CursorPage {
+        nodes: cursor_page.nodes,
+        ..Default::default()
+    };
+    let connection = Connection::from_page_with(hand_built, |node| Cursor::encode(&keys(node)));
+
+    assert_eq!(connection.edges.len(), 2);
+    assert_eq!(connection.edges[1].cursor, Cursor::encode(&2));
+}
+
+#[test]
+fn connections_reject_pages_without_cursors() {
+    let cursor_page = CursorPage {
+        nodes: NODES.to_vec(),
+        ..CursorPage::<_, Cursor>::default()
+    };
+
+    assert!(!serde_json::to_string(&cursor_page).unwrap().contains("\"cursors\""));
+    assert_eq!(Connection::try_from(cursor_page).err(), Some(CursorError::Missing));
+}
+
+#[test]
+fn offset_pages_count_every_node() {
+    let nodes = (1..=23).collect::<Vec<u32>>();
+
+    for (page, per_page, expected) in [
+        (1, 10, (1..=10).collect::<Vec<_>>()),
+        (3, 10, vec![21, 22, 23]),
+        (4, 10, vec![]),
+        (0, 5, vec![1, 2, 3, 4, 5]),
+        (2, 0, vec![]),
+    ] {
+        let offset_page_params = OffsetPageParams::new(page, per_page);
+        let from_slice = OffsetPage::from_slice(&offset_page_params, &nodes);
+        let from_iter = OffsetPage::from_iter(&offset_page_params, nodes.iter().copied());
+
+        assert_eq!(from_slice.nodes, expected);
+        assert_eq!(from_iter.nodes, expected);
+        assert_eq!(from_slice.total_count, 23);
+        assert_eq!(from_iter.total_count, 23);
+        assert_eq!(from_iter.total_pages, from_slice.total_pages);
+    }
+
+    let last = OffsetPage::from_slice(&OffsetPageParams::new(3, 10), &nodes);
+
+    assert_eq!(last.total_pages, 3);
+    assert!(!last.has_next_page() && last.has_previous_page());
+}
+
+#[test]
+fn cursor_page_params_reject_mixed_directions() {
+    let bounds = PageSizeBounds {
+        default: 10,
+        max: 20,
+        min: 1,
+    };
+    let cursor = || Some(Cursor::encode(&5));
+
+    for (cursor_page_params, fields) in [
+        (CursorPageParams::new(cursor(), 5), vec![]),
+        (CursorPageParams::new_before(cursor(), 5), vec![]),
+        (CursorPageParams::new(None, 21), vec!["first"]),
+        (
+            CursorPageParams {
+                before: cursor(),
+                ..CursorPageParams::new(cursor(), 5)
+            },
+            vec!["before", "first"],
+        ),
+        (
+            CursorPageParams {
+                after: cursor(),
+                ..CursorPageParams::new_before(None, 5)
+            },
+            vec!["last"],
+        ),
+        (
+            CursorPageParams {
+                first: Some(5),
+                ..CursorPageParams::new_before(cursor(), 5)
+            },
+            vec!["first"],
+        ),
+    ] {
+        let errors = cursor_page_params.validate_with(&bounds).err().unwrap_or_default();
+        let mut error_fields = errors.field_errors().into_keys().collect::<Vec<_>>();
+
+        error_fields.sort();
+
+        assert_eq!(error_fields, fields);
+    }
+}
+
+#[test]
+fn page_sizes_left_out_are_filled_with_the_default() {
+    let mut forward = CursorPageParams::<Cursor>::default();
+    let mut backward = CursorPageParams::new_before(None, 4);
+    let mut offset = OffsetPageParams::default();
+
+    backward.last = None;
+    backward.before = Some(Cursor::encode(&5));
+
+    forward.fill_page_size(7);
+    backward.fill_page_size(7);
+    offset.fill_page_size(7);
+
+    assert_eq!((forward.first, forward.last), (Some(7), None));
+    assert_eq!((backward.first, backward.last), (None, Some(7)));
+    assert_eq!(offset.per_page(), 7);
+}